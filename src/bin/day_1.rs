@@ -1,85 +1,61 @@
-use std::collections::HashMap;
-use std::iter::zip;
+use advent_2024::cli;
+use advent_2024::days::day_1::{
+    pairwise, parse_columns, parse_input, solve_part_1, solve_part_2, solve_stream, Metric,
+    DEFAULT_CHUNK_LINES,
+};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
 
-fn solve_part_1(lhs_list: &[i32], rhs_list: &[i32]) -> i32 {
-    let mut lhs_list_sorted = lhs_list.to_vec();
-    lhs_list_sorted.sort();
-    let mut rhs_list_sorted = rhs_list.to_vec();
-    rhs_list_sorted.sort();
-
-    let mut result = 0;
-    for (lhs, rhs) in zip(lhs_list_sorted, rhs_list_sorted) {
-        result += (lhs - rhs).abs();
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if let Some(path) = cli::option(&args, "--stream") {
+        let chunk_lines = cli::option(&args, "--chunk-lines")
+            .map(|c| c.parse().expect("--chunk-lines expects a number of lines"))
+            .unwrap_or(DEFAULT_CHUNK_LINES);
+        let reader: Box<dyn BufRead> = match path {
+            "-" => Box::new(io::stdin().lock()),
+            _ => Box::new(BufReader::new(File::open(path).unwrap())),
+        };
+        let totals = solve_stream(reader, chunk_lines).unwrap_or_else(|e| panic!("{}", e));
+
+        println!("part 1: {}", totals.distance);
+        println!("part 2: {}", totals.similarity);
+        return;
     }
 
-    result
-}
-
-fn solve_part_2(lhs_list: &[i32], rhs_list: &[i32]) -> i32 {
-    let mut rhs_counts = HashMap::new();
-
-    for i in rhs_list {
-        let count = rhs_counts.entry(*i).or_insert(0);
-        *count += 1;
+    if let Some(path) = cli::option(&args, "--columns") {
+        let mut input = String::new();
+        match path {
+            "-" => io::stdin().read_to_string(&mut input).map(|_| ()),
+            _ => fs::read_to_string(path).map(|text| input = text),
+        }
+        .unwrap();
+        let columns = parse_columns(&input).unwrap_or_else(|e| panic!("{}", e));
+
+        println!("part 1 distances:\n{}", pairwise(&columns, solve_part_1));
+        print!("part 2 similarities:\n{}", pairwise(&columns, solve_part_2));
+        return;
     }
 
-    let mut result = 0;
-
-    for i in lhs_list {
-        let rhs_count = rhs_counts.entry(*i).or_insert(0);
-        result += i * *rhs_count;
-    }
+    let input = include_str!("day_1_input.txt");
+    let (lhs_list, rhs_list) = parse_input(input).unwrap_or_else(|e| panic!("{}", e));
 
-    result
-}
+    if let Some(names) = cli::option(&args, "--metric") {
+        for name in names.split(',') {
+            let metric: Metric = name.parse().unwrap_or_else(|e: String| panic!("{}", e));
 
-fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
-    let mut lhs_list = vec![];
-    let mut rhs_list = vec![];
+            let value = metric.evaluate(&lhs_list, &rhs_list);
 
-    for line in input.lines() {
-        let mut parts = line.split_whitespace();
-        lhs_list.push(parts.next().unwrap().parse().unwrap());
-        rhs_list.push(parts.next().unwrap().parse().unwrap());
+            if value.contains('\n') {
+                print!("{}:\n{}", name, value);
+            } else {
+                println!("{}: {}", name, value);
+            }
+        }
+        return;
     }
 
-    (lhs_list, rhs_list)
-}
-
-fn main() {
-    let input = include_str!("day_1_input.txt");
-    let (lhs_list, rhs_list) = parse_input(&input);
-
     println!("part 1: {}", solve_part_1(&lhs_list, &rhs_list));
     println!("part 2: {}", solve_part_2(&lhs_list, &rhs_list));
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::{parse_input, solve_part_1};
-
-    #[test]
-    fn test_solve_part_1() {
-        assert_eq!(
-            11,
-            solve_part_1(&vec![3, 4, 2, 1, 3, 3], &vec![4, 3, 5, 3, 9, 3])
-        );
-    }
-
-    #[test]
-    fn test_parse() {
-        let input = "1 2\n3 4";
-        let (lhs_list, rhs_list) = parse_input(input);
-
-        assert_eq!(vec![1, 3], lhs_list);
-        assert_eq!(vec![2, 4], rhs_list);
-    }
-
-    #[test]
-    fn test_solve_part_2() {
-        assert_eq!(
-            31,
-            crate::solve_part_2(&vec![3, 4, 2, 1, 3, 3], &vec![4, 3, 5, 3, 9, 3])
-        );
-    }
-}
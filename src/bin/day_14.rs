@@ -1,185 +1,54 @@
-use itertools::Itertools;
-use std::ops::{Add, Mul};
-
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
-struct Vector {
-    x: i32,
-    y: i32,
-}
-
-impl Mul<i32> for Vector {
-    type Output = Vector;
-
-    fn mul(self, scalar: i32) -> Vector {
-        Vector {
-            x: self.x * scalar,
-            y: self.y * scalar,
-        }
-    }
-}
-
-impl Add for Vector {
-    type Output = Vector;
-
-    fn add(self, rhs: Vector) -> Vector {
-        Vector {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl Vector {
-    fn clamp(self, within: &Vector) -> Vector {
-        fn clamp_int(max: i32, x: i32) -> i32 {
-            let x = x % max;
-
-            if x < 0 {
-                x + max
-            } else {
-                x
-            }
-        }
-
-        Vector {
-            x: clamp_int(within.x, self.x),
-            y: clamp_int(within.y, self.y),
-        }
-    }
-}
-
-struct Robot {
-    position: Vector,
-    velocity: Vector,
-}
-
-fn parse_input(input: &str) -> Vec<Robot> {
-    fn parse_vector(vector: &str) -> Vector {
-        let parts: Vec<i32> = vector
-            .split(&[',', '='])
-            .skip(1)
-            .map(|s| s.parse().unwrap())
-            .collect();
-
-        Vector {
-            x: parts[0],
-            y: parts[1],
-        }
-    }
-
-    input
-        .lines()
-        .map(|l| {
-            let (position, velocity) = l.split(" ").collect_tuple().unwrap();
-
-            Robot {
-                position: parse_vector(position),
-                velocity: parse_vector(velocity),
-            }
-        })
-        .collect()
-}
-
-fn solve_part_1(bounds: &Vector, robots: &[Robot]) -> i32 {
-    let (mx, my): (i32, i32) = (bounds.x / 2, bounds.y / 2);
-    let final_positions = robots
-        .iter()
-        .map(|r| (r.position + r.velocity * 100).clamp(bounds))
-        .collect::<Vec<Vector>>();
-
-    let (mut ne, mut nw, mut sw, mut se): (i32, i32, i32, i32) = (0, 0, 0, 0);
-
-    for v in final_positions {
-        if v.x < mx {
-            if v.y < my {
-                nw += 1;
-            } else if v.y > my {
-                sw += 1;
-            }
-        } else if v.x > mx {
-            if v.y < my {
-                ne += 1;
-            } else if v.y > my {
-                se += 1;
-            }
-        }
-    }
-
-    ne * nw * sw * se
-}
-
-fn solve_part_2(bounds: &Vector, robots: &[Robot]) -> i32 {
-    'next_second: for seconds in 1..i32::MAX {
-        let mut positions = vec![false; (bounds.x * bounds.y) as usize];
-
-        for p in robots {
-            let position = (p.position + p.velocity * seconds).clamp(bounds);
-            let i = position.x + position.y * bounds.x;
-
-            if positions[i as usize] {
-                continue 'next_second;
-            } else {
-                positions[i as usize] = true;
-            }
-        }
-
-        return seconds;
-    }
-
-    -1
-}
+use advent_2024::cli;
+use advent_2024::days::day_14::{
+    parse_input, robot_image, solve_part_1, solve_part_2, Lobby, Vector,
+};
+use advent_2024::image::save_frames;
+use advent_2024::terminal::{Animation, Colour};
+use std::path::Path;
 
 fn main() {
     let robots = parse_input(include_str!("day_14_input.txt"));
+    let bounds = Vector { x: 101, y: 103 };
 
-    println!(
-        "part 1: {}",
-        solve_part_1(&Vector { x: 101, y: 103 }, &robots)
-    );
-    println!(
-        "part 2: {}",
-        solve_part_2(&Vector { x: 101, y: 103 }, &robots)
-    );
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{parse_input, solve_part_1, Vector};
-
-    const SMALL_EXAMPLE: &str = "p=0,4 v=3,-3\n\
-        p=6,3 v=-1,-3\n\
-        p=10,3 v=-1,2\n\
-        p=2,0 v=2,-1\n\
-        p=0,0 v=1,3\n\
-        p=3,0 v=-2,-2\n\
-        p=7,6 v=-1,-3\n\
-        p=3,0 v=-1,-2\n\
-        p=9,3 v=2,3\n\
-        p=7,3 v=-1,2\n\
-        p=2,4 v=2,-3\n\
-        p=9,5 v=-3,-3\n";
+    let args: Vec<String> = std::env::args().collect();
+    let start = cli::option(&args, "--start")
+        .map(|s| s.parse().expect("--start expects a number of seconds"))
+        .unwrap_or(0);
 
-    #[test]
-    fn test_parse_input_on_small_example() {
-        let robots = parse_input(SMALL_EXAMPLE);
-        let first_robot = &robots[0];
+    if let Some(animation) = Animation::from_args(&args) {
+        let palette = "123456789+".chars().map(|c| (c, Colour::Green)).collect();
+        let mut lobby = Lobby::new(bounds, &robots, start);
 
-        assert_eq!(Vector { x: 0, y: 4 }, first_robot.position);
-        assert_eq!(Vector { x: 3, y: -3 }, first_robot.velocity);
+        animation.with_palette(palette).play(&mut lobby).unwrap();
+        println!("seconds: {}", lobby.seconds());
+        return;
     }
 
-    #[test]
-    fn test_clamp() {
-        assert_eq!(
-            Vector { x: 9, y: 1 },
-            Vector { x: -1, y: 6 }.clamp(&Vector { x: 10, y: 5 })
-        )
+    if let Some(path) = cli::option(&args, "--image") {
+        let seconds = cli::option(&args, "--at")
+            .map(|s| s.parse().expect("--at expects a number of seconds"))
+            .unwrap_or_else(|| solve_part_2(&bounds, &robots));
+
+        robot_image(&bounds, &robots, seconds)
+            .scaled(4)
+            .save(Path::new(path))
+            .unwrap();
+        println!("wrote robots at {} seconds to {}", seconds, path);
+        return;
     }
 
-    #[test]
-    fn test_solve_part_1() {
-        let robots = parse_input(SMALL_EXAMPLE);
+    if let Some(directory) = cli::option(&args, "--frames") {
+        let count = cli::option(&args, "--count")
+            .map(|s| s.parse().expect("--count expects a number of frames"))
+            .unwrap_or(100);
+        let palette = "123456789+".chars().map(|c| (c, [0, 200, 0])).collect();
+        let mut lobby = Lobby::new(bounds, &robots, start);
 
-        assert_eq!(12, solve_part_1(&Vector { x: 11, y: 7 }, &robots))
+        let written = save_frames(&mut lobby, count, &palette, 4, Path::new(directory)).unwrap();
+        println!("wrote {} frames to {}", written, directory);
+        return;
     }
+
+    println!("part 1: {}", solve_part_1(&bounds, &robots));
+    println!("part 2: {}", solve_part_2(&bounds, &robots));
 }
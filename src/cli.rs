@@ -0,0 +1,28 @@
+/// True when `name` appears anywhere in the arguments.
+pub fn flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|a| a == name)
+}
+
+/// The argument following `name`, for options like `--fps 20`.
+pub fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{flag, option};
+
+    #[test]
+    fn test_flag_and_option() {
+        let args = ["day_14", "--image", "tree.png", "--at"].map(String::from);
+
+        assert!(flag(&args, "--image"));
+        assert!(!flag(&args, "--animate"));
+        assert_eq!(Some("tree.png"), option(&args, "--image"));
+        assert_eq!(None, option(&args, "--at"));
+        assert_eq!(None, option(&args, "--fps"));
+    }
+}
@@ -0,0 +1,797 @@
+//! Day 1, Historian Hysteria: distances and similarity between two location lists.
+
+use crate::solver::{Params, SolveError, Solver};
+use itertools::{process_results, EitherOrBoth, Itertools};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::fmt::Formatter;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::iter::zip;
+use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Pairs held in memory before a sorted run is spilled to disk when streaming.
+pub const DEFAULT_CHUNK_LINES: usize = 1 << 20;
+
+pub fn solve_part_1(lhs_list: &[i32], rhs_list: &[i32]) -> i32 {
+    let mut lhs_list_sorted = lhs_list.to_vec();
+    lhs_list_sorted.sort();
+    let mut rhs_list_sorted = rhs_list.to_vec();
+    rhs_list_sorted.sort();
+
+    let mut result = 0;
+    for (lhs, rhs) in zip(lhs_list_sorted, rhs_list_sorted) {
+        result += (lhs - rhs).abs();
+    }
+
+    result
+}
+
+pub fn solve_part_2(lhs_list: &[i32], rhs_list: &[i32]) -> i32 {
+    let mut rhs_counts = HashMap::new();
+
+    for i in rhs_list {
+        let count = rhs_counts.entry(*i).or_insert(0);
+        *count += 1;
+    }
+
+    let mut result = 0;
+
+    for i in lhs_list {
+        let rhs_count = rhs_counts.entry(*i).or_insert(0);
+        result += i * *rhs_count;
+    }
+
+    result
+}
+
+/// Reads the first two columns; see `parse_columns` for more than two.
+pub fn parse_input(input: &str) -> Result<(Vec<i32>, Vec<i32>), String> {
+    let mut columns = parse_columns(input)?;
+    if columns.lists.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+    if columns.lists.len() < 2 {
+        return Err(format!(
+            "expected two columns, found {}",
+            columns.lists.len()
+        ));
+    }
+    let rhs_list = columns.lists.swap_remove(1);
+    let lhs_list = columns.lists.swap_remove(0);
+
+    Ok((lhs_list, rhs_list))
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Columns {
+    pub names: Vec<String>,
+    pub lists: Vec<Vec<i32>>,
+}
+
+/// Parses any number of whitespace separated columns. A first line with no numbers at all is
+/// taken as a header naming them, otherwise they are numbered from 1.
+pub fn parse_columns(input: &str) -> Result<Columns, String> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .peekable();
+
+    let first = lines
+        .peek()
+        .map(|(_, line)| line.split_whitespace().collect_vec());
+    let header = first.filter(|fields| fields.iter().all(|f| f.parse::<i32>().is_err()));
+    let names = match header {
+        Some(fields) => {
+            lines.next();
+            fields.iter().map(|f| f.to_string()).collect_vec()
+        }
+        None => {
+            let width = lines
+                .peek()
+                .map_or(0, |(_, line)| line.split_whitespace().count());
+            (1..=width).map(|n| n.to_string()).collect_vec()
+        }
+    };
+
+    let mut lists = vec![vec![]; names.len()];
+    for (index, line) in lines {
+        let fields = line.split_whitespace().collect_vec();
+        if fields.len() != names.len() {
+            return Err(format!(
+                "line {} has {} columns, expected {}",
+                index + 1,
+                fields.len(),
+                names.len()
+            ));
+        }
+
+        for (list, field) in zip(&mut lists, fields) {
+            list.push(
+                field
+                    .parse()
+                    .map_err(|_| format!("line {}: '{}' is not a number", index + 1, field))?,
+            );
+        }
+    }
+
+    if !names.is_empty() && lists.iter().all(|list| list.is_empty()) {
+        return Err("a header needs at least one row of numbers under it".to_string());
+    }
+
+    Ok(Columns { names, lists })
+}
+
+/// A comparison of every column (rows) against every other column (columns).
+#[derive(Debug, PartialEq)]
+pub struct Matrix {
+    pub names: Vec<String>,
+    pub values: Vec<Vec<i32>>,
+}
+
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self
+            .values
+            .iter()
+            .flatten()
+            .map(|v| v.to_string().len())
+            .chain(self.names.iter().map(|n| n.len()))
+            .max()
+            .unwrap_or(0);
+
+        write!(f, "{:width$}", "")?;
+        for name in &self.names {
+            write!(f, " {:>width$}", name)?;
+        }
+        writeln!(f)?;
+
+        for (name, row) in zip(&self.names, &self.values) {
+            write!(f, "{:width$}", name)?;
+            for value in row {
+                write!(f, " {:>width$}", value)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies a two-list solver, such as `solve_part_1`, to every ordered pair of columns.
+pub fn pairwise(columns: &Columns, solve: fn(&[i32], &[i32]) -> i32) -> Matrix {
+    Matrix {
+        names: columns.names.clone(),
+        values: columns
+            .lists
+            .iter()
+            .map(|lhs| columns.lists.iter().map(|rhs| solve(lhs, rhs)).collect())
+            .collect(),
+    }
+}
+
+fn sorted(list: &[i32]) -> Vec<i64> {
+    list.iter().map(|&id| id as i64).sorted().collect()
+}
+
+fn counts(list: &[i32]) -> HashMap<i32, usize> {
+    list.iter().copied().counts()
+}
+
+/// Distinct IDs found in both lists as a fraction of the distinct IDs found in either. Two empty
+/// lists have nothing to tell them apart, so they overlap completely at 1.0.
+pub fn jaccard_overlap(lhs_list: &[i32], rhs_list: &[i32]) -> f64 {
+    let lhs = lhs_list.iter().collect::<HashSet<_>>();
+    let rhs = rhs_list.iter().collect::<HashSet<_>>();
+    let union = lhs.union(&rhs).count();
+
+    if union == 0 {
+        return 1.0;
+    }
+
+    lhs.intersection(&rhs).count() as f64 / union as f64
+}
+
+/// How many IDs are left over once each occurrence is matched with one in the other list.
+pub fn symmetric_difference(lhs_list: &[i32], rhs_list: &[i32]) -> usize {
+    let diff = diff_report(lhs_list, rhs_list);
+
+    diff.lhs_only.len() + diff.rhs_only.len()
+}
+
+pub fn squared_distance(lhs_list: &[i32], rhs_list: &[i32]) -> i128 {
+    zip(sorted(lhs_list), sorted(rhs_list))
+        .map(|(lhs, rhs)| ((lhs - rhs) as i128).pow(2))
+        .sum()
+}
+
+/// Median of `rhs - lhs` over the sorted pairs, or `None` when there are no pairs.
+pub fn median_offset(lhs_list: &[i32], rhs_list: &[i32]) -> Option<f64> {
+    let offsets = zip(sorted(lhs_list), sorted(rhs_list))
+        .map(|(lhs, rhs)| rhs - lhs)
+        .sorted()
+        .collect_vec();
+    let middle = offsets.len() / 2;
+
+    match offsets.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(offsets[middle] as f64),
+        _ => Some((offsets[middle - 1] + offsets[middle]) as f64 / 2.0),
+    }
+}
+
+/// The IDs, one entry per occurrence, that have no partner in the other list.
+#[derive(Debug, PartialEq)]
+pub struct DiffReport {
+    pub lhs_only: Vec<i32>,
+    pub rhs_only: Vec<i32>,
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for id in &self.lhs_only {
+            writeln!(f, "< {}", id)?;
+        }
+        for id in &self.rhs_only {
+            writeln!(f, "> {}", id)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn diff_report(lhs_list: &[i32], rhs_list: &[i32]) -> DiffReport {
+    let lhs_counts = counts(lhs_list);
+    let rhs_counts = counts(rhs_list);
+    let unmatched = |counts: &HashMap<i32, usize>, other: &HashMap<i32, usize>| {
+        counts
+            .iter()
+            .flat_map(|(id, count)| {
+                let excess = count.saturating_sub(*other.get(id).unwrap_or(&0));
+                std::iter::repeat_n(*id, excess)
+            })
+            .sorted()
+            .collect_vec()
+    };
+
+    DiffReport {
+        lhs_only: unmatched(&lhs_counts, &rhs_counts),
+        rhs_only: unmatched(&rhs_counts, &lhs_counts),
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Metric {
+    Distance,
+    Similarity,
+    Jaccard,
+    SymmetricDifference,
+    SquaredDistance,
+    MedianOffset,
+    Diff,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 7] = [
+        Metric::Distance,
+        Metric::Similarity,
+        Metric::Jaccard,
+        Metric::SymmetricDifference,
+        Metric::SquaredDistance,
+        Metric::MedianOffset,
+        Metric::Diff,
+    ];
+
+    /// The name `--metric` knows it by.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Metric::Distance => "distance",
+            Metric::Similarity => "similarity",
+            Metric::Jaccard => "jaccard",
+            Metric::SymmetricDifference => "symmetric-difference",
+            Metric::SquaredDistance => "squared-distance",
+            Metric::MedianOffset => "median-offset",
+            Metric::Diff => "diff",
+        }
+    }
+
+    pub fn evaluate(&self, lhs_list: &[i32], rhs_list: &[i32]) -> String {
+        match self {
+            Metric::Distance => solve_part_1(lhs_list, rhs_list).to_string(),
+            Metric::Similarity => solve_part_2(lhs_list, rhs_list).to_string(),
+            Metric::Jaccard => format!("{:.6}", jaccard_overlap(lhs_list, rhs_list)),
+            Metric::SymmetricDifference => symmetric_difference(lhs_list, rhs_list).to_string(),
+            Metric::SquaredDistance => squared_distance(lhs_list, rhs_list).to_string(),
+            Metric::MedianOffset => median_offset(lhs_list, rhs_list)
+                .map(|m| m.to_string())
+                .unwrap_or("none".to_string()),
+            Metric::Diff => diff_report(lhs_list, rhs_list).to_string(),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Metric::ALL
+            .into_iter()
+            .find(|metric| metric.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown metric '{}', expected one of {}",
+                    s,
+                    Metric::ALL.iter().map(Metric::name).join(", ")
+                )
+            })
+    }
+}
+
+/// Both answers, accumulated wide enough that multi-gigabyte lists cannot overflow.
+#[derive(Debug, PartialEq, Default)]
+pub struct Totals {
+    pub distance: i128,
+    pub similarity: i128,
+}
+
+/// Solves both parts without holding the lists in memory: each column is sorted in chunks of
+/// `chunk_lines`, spilled to temporary files and merged back, once for the pairwise distance
+/// and once to match up equal IDs for the similarity. Runs are merged a bounded number at a time,
+/// in several passes if needed, so huge inputs don't run out of file handles.
+pub fn solve_stream<R: BufRead>(reader: R, chunk_lines: usize) -> io::Result<Totals> {
+    solve_stream_merging(reader, chunk_lines, MERGE_WIDTH)
+}
+
+// At most this many runs of a column are merged at once, to stay well inside file handle limits.
+const MERGE_WIDTH: usize = 64;
+
+fn solve_stream_merging<R: BufRead>(
+    reader: R,
+    chunk_lines: usize,
+    merge_width: usize,
+) -> io::Result<Totals> {
+    if chunk_lines == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "chunks need at least one line",
+        ));
+    }
+
+    let mut spill = Spill::default();
+    let mut lhs_runs = vec![];
+    let mut rhs_runs = vec![];
+    let mut lhs_chunk = vec![];
+    let mut rhs_chunk = vec![];
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let mut parts = line.split_whitespace();
+        let Some(lhs) = parts.next() else {
+            continue;
+        };
+
+        lhs_chunk.push(parse_id(Some(lhs), index)?);
+        rhs_chunk.push(parse_id(parts.next(), index)?);
+
+        if lhs_chunk.len() >= chunk_lines {
+            lhs_runs.push(spill.write_run(&mut lhs_chunk)?);
+            rhs_runs.push(spill.write_run(&mut rhs_chunk)?);
+        }
+    }
+
+    lhs_chunk.sort_unstable();
+    rhs_chunk.sort_unstable();
+    lhs_runs.push(Run::Memory(lhs_chunk));
+    rhs_runs.push(Run::Memory(rhs_chunk));
+    let lhs_runs = reduce_runs(&mut spill, lhs_runs, merge_width)?;
+    let rhs_runs = reduce_runs(&mut spill, rhs_runs, merge_width)?;
+
+    let distance = process_results(Merge::new(&lhs_runs)?, |lhs| {
+        process_results(Merge::new(&rhs_runs)?, |rhs| {
+            zip(lhs, rhs)
+                .map(|(l, r)| (l as i128 - r as i128).abs())
+                .sum()
+        })
+    })??;
+
+    let similarity = process_results(Merge::new(&lhs_runs)?, |lhs| {
+        process_results(Merge::new(&rhs_runs)?, |rhs| {
+            lhs.dedup_with_count()
+                .merge_join_by(rhs.dedup_with_count(), |(_, l), (_, r)| l.cmp(r))
+                .map(|pair| match pair {
+                    EitherOrBoth::Both((lhs_count, id), (rhs_count, _)) => {
+                        id as i128 * lhs_count as i128 * rhs_count as i128
+                    }
+                    _ => 0,
+                })
+                .sum()
+        })
+    })??;
+
+    Ok(Totals {
+        distance,
+        similarity,
+    })
+}
+
+// Merges runs `width` at a time into longer ones until there are few enough to merge in one go.
+fn reduce_runs(spill: &mut Spill, mut runs: Vec<Run>, width: usize) -> io::Result<Vec<Run>> {
+    while runs.len() > width {
+        let mut merged = vec![];
+
+        for group in runs.chunks(width) {
+            let (path, mut out) = spill.create_run()?;
+            for id in Merge::new(group)? {
+                out.write_all(&id?.to_le_bytes())?;
+            }
+            out.flush()?;
+
+            merged.push(Run::File(path));
+        }
+
+        for run in runs {
+            if let Run::File(path) = run {
+                fs::remove_file(path)?;
+            }
+        }
+        runs = merged;
+    }
+
+    Ok(runs)
+}
+
+fn parse_id(field: Option<&str>, index: usize) -> io::Result<i64> {
+    field.and_then(|f| f.parse().ok()).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("line {} should hold two location IDs", index + 1),
+        )
+    })
+}
+
+enum Run {
+    Memory(Vec<i64>),
+    File(PathBuf),
+}
+
+/// Temporary directory for sorted runs, created on the first spill and removed when dropped.
+#[derive(Default)]
+struct Spill {
+    directory: Option<PathBuf>,
+    runs: usize,
+}
+
+impl Spill {
+    fn write_run(&mut self, chunk: &mut Vec<i64>) -> io::Result<Run> {
+        chunk.sort_unstable();
+
+        let (path, mut out) = self.create_run()?;
+        for id in chunk.drain(..) {
+            out.write_all(&id.to_le_bytes())?;
+        }
+        out.flush()?;
+
+        Ok(Run::File(path))
+    }
+
+    fn create_run(&mut self) -> io::Result<(PathBuf, BufWriter<File>)> {
+        static SPILLS: AtomicUsize = AtomicUsize::new(0);
+
+        if self.directory.is_none() {
+            let directory = std::env::temp_dir().join(format!(
+                "advent_2024_day_1_{}_{}",
+                process::id(),
+                SPILLS.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&directory)?;
+            self.directory = Some(directory);
+        }
+
+        let path = self
+            .directory
+            .as_ref()
+            .unwrap()
+            .join(format!("run_{}", self.runs));
+        self.runs += 1;
+        let out = BufWriter::new(File::create(&path)?);
+
+        Ok((path, out))
+    }
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) {
+        if let Some(directory) = &self.directory {
+            let _ = fs::remove_dir_all(directory);
+        }
+    }
+}
+
+enum RunReader<'a> {
+    Memory(std::slice::Iter<'a, i64>),
+    File(BufReader<File>),
+}
+
+impl RunReader<'_> {
+    fn next_id(&mut self) -> io::Result<Option<i64>> {
+        match self {
+            RunReader::Memory(ids) => Ok(ids.next().copied()),
+            RunReader::File(reader) => {
+                let mut bytes = [0; 8];
+                match reader.read_exact(&mut bytes) {
+                    Ok(()) => Ok(Some(i64::from_le_bytes(bytes))),
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+    }
+}
+
+/// K-way merge of sorted runs into one ascending sequence of IDs.
+struct Merge<'a> {
+    readers: Vec<RunReader<'a>>,
+    heads: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl<'a> Merge<'a> {
+    fn new(runs: &'a [Run]) -> io::Result<Self> {
+        let mut readers = runs
+            .iter()
+            .map(|run| match run {
+                Run::Memory(ids) => Ok(RunReader::Memory(ids.iter())),
+                Run::File(path) => Ok(RunReader::File(BufReader::new(File::open(path)?))),
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut heads = BinaryHeap::new();
+        for (index, reader) in readers.iter_mut().enumerate() {
+            if let Some(id) = reader.next_id()? {
+                heads.push(Reverse((id, index)));
+            }
+        }
+
+        Ok(Merge { readers, heads })
+    }
+}
+
+impl Iterator for Merge<'_> {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((id, index)) = self.heads.pop()?;
+
+        match self.readers[index].next_id() {
+            Ok(Some(next)) => self.heads.push(Reverse((next, index))),
+            Ok(None) => {}
+            Err(e) => return Some(Err(e)),
+        }
+
+        Some(Ok(id))
+    }
+}
+
+pub struct Day1;
+
+impl Solver for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn solve(&self, part: u8, input: &str, _params: &Params) -> Result<String, SolveError> {
+        let (lhs_list, rhs_list) = parse_input(input).map_err(|_| SolveError::InvalidInput)?;
+
+        match part {
+            1 => Ok(solve_part_1(&lhs_list, &rhs_list).to_string()),
+            2 => Ok(solve_part_2(&lhs_list, &rhs_list).to_string()),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_1::{
+        diff_report, jaccard_overlap, median_offset, pairwise, parse_columns, parse_input,
+        solve_part_1, solve_part_2, solve_stream, solve_stream_merging, squared_distance,
+        symmetric_difference, Columns, DiffReport, Matrix, Metric, Totals,
+    };
+    use rstest::rstest;
+
+    #[test]
+    fn test_solve_part_1() {
+        assert_eq!(11, solve_part_1(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3]));
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = "1 2\n3 4";
+        let (lhs_list, rhs_list) = parse_input(input).unwrap();
+
+        assert_eq!(vec![1, 3], lhs_list);
+        assert_eq!(vec![2, 4], rhs_list);
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert_eq!(Ok((vec![], vec![])), parse_input(""));
+        assert_eq!(
+            Err("expected two columns, found 1".to_string()),
+            parse_input("1\n2\n")
+        );
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        assert_eq!(
+            31,
+            crate::days::day_1::solve_part_2(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3])
+        );
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(100)]
+    fn test_solve_stream(#[case] chunk_lines: usize) {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        assert_eq!(
+            Totals {
+                distance: 11,
+                similarity: 31
+            },
+            solve_stream(input.as_bytes(), chunk_lines).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case(1, 2)]
+    #[case(1, 3)]
+    #[case(2, 2)]
+    fn test_solve_stream_merging_in_passes(#[case] chunk_lines: usize, #[case] width: usize) {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+        assert_eq!(
+            Totals {
+                distance: 11,
+                similarity: 31
+            },
+            solve_stream_merging(input.as_bytes(), chunk_lines, width).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_rejects_empty_chunks() {
+        assert!(solve_stream("1 2\n".as_bytes(), 0).is_err());
+    }
+
+    #[test]
+    fn test_solve_stream_beyond_i32() {
+        let input = "3000000000 -3000000000\n3000000000 3000000000\n";
+
+        assert_eq!(
+            Totals {
+                distance: 6000000000,
+                similarity: 6000000000
+            },
+            solve_stream(input.as_bytes(), 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_rejects_bad_line() {
+        assert!(solve_stream("1 2\n3\n".as_bytes(), 10).is_err());
+    }
+
+    const LHS: [i32; 6] = [3, 4, 2, 1, 3, 3];
+    const RHS: [i32; 6] = [4, 3, 5, 3, 9, 3];
+
+    #[test]
+    fn test_metrics() {
+        assert_eq!(1.0 / 3.0, jaccard_overlap(&LHS, &RHS));
+        assert_eq!(4, symmetric_difference(&LHS, &RHS));
+        assert_eq!(35, squared_distance(&LHS, &RHS));
+        assert_eq!(Some(1.5), median_offset(&LHS, &RHS));
+        assert_eq!(None, median_offset(&[], &[]));
+    }
+
+    #[test]
+    fn test_jaccard_overlap_of_empty_lists() {
+        assert_eq!(1.0, jaccard_overlap(&[], &[]));
+        assert_eq!(0.0, jaccard_overlap(&[1], &[]));
+    }
+
+    #[test]
+    fn test_diff_report() {
+        let report = diff_report(&LHS, &RHS);
+
+        assert_eq!(
+            DiffReport {
+                lhs_only: vec![1, 2],
+                rhs_only: vec![5, 9],
+            },
+            report
+        );
+        assert_eq!("< 1\n< 2\n> 5\n> 9\n", report.to_string());
+    }
+
+    #[rstest]
+    #[case("distance", "11")]
+    #[case("similarity", "31")]
+    #[case("jaccard", "0.333333")]
+    #[case("median-offset", "1.5")]
+    fn test_metric_by_name(#[case] name: &str, #[case] expected: &str) {
+        let metric: Metric = name.parse().unwrap();
+
+        assert_eq!(expected, metric.evaluate(&LHS, &RHS));
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        assert_eq!(
+            Err(
+                "unknown metric 'manhattan', expected one of distance, similarity, jaccard, \
+                symmetric-difference, squared-distance, median-offset, diff"
+                    .to_string()
+            ),
+            "manhattan".parse::<Metric>()
+        );
+    }
+
+    #[test]
+    fn test_metric_names_round_trip() {
+        for metric in Metric::ALL {
+            assert_eq!(Ok(metric), metric.name().parse());
+        }
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(
+            Ok(Columns {
+                names: vec!["1".to_string(), "2".to_string(), "3".to_string()],
+                lists: vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            }),
+            parse_columns("1 2 3\n4 5 6\n")
+        );
+        assert_eq!(
+            Ok(Columns {
+                names: vec!["east".to_string(), "west".to_string()],
+                lists: vec![vec![1], vec![2]],
+            }),
+            parse_columns("east west\n1 2\n")
+        );
+        assert_eq!(
+            Err("line 2 has 2 columns, expected 3".to_string()),
+            parse_columns("1 2 3\n4 5\n")
+        );
+        assert!(parse_columns("not numbers").is_err());
+        assert_eq!(
+            Err("line 1: 'x' is not a number".to_string()),
+            parse_columns("3 x\n4 5\n")
+        );
+    }
+
+    #[test]
+    fn test_pairwise() {
+        let columns = parse_columns("a b c\n3 4 3\n4 3 3\n2 5 1\n").unwrap();
+
+        assert_eq!(
+            Matrix {
+                names: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                values: vec![vec![0, 3, 2], vec![3, 0, 5], vec![2, 5, 0]],
+            },
+            pairwise(&columns, solve_part_1)
+        );
+        assert_eq!(
+            "    a  b  c\na   9  7  6\nb   7 12  6\nc   6  6 13\n",
+            pairwise(&columns, solve_part_2).to_string()
+        );
+    }
+}
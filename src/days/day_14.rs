@@ -0,0 +1,331 @@
+//! Day 14, Restroom Redoubt: robots wrapping around the lobby, and the Christmas tree they form.
+
+use crate::image::{Image, BLACK};
+use crate::solver::{check_lines, param_in, Params, SolveError, Solver};
+use crate::terminal::Simulation;
+use itertools::Itertools;
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::{Add, Mul};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Vector {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Mul<i32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, scalar: i32) -> Vector {
+        Vector {
+            x: self.x * scalar,
+            y: self.y * scalar,
+        }
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
+}
+
+impl Vector {
+    fn clamp(self, within: &Vector) -> Vector {
+        fn clamp_int(max: i32, x: i32) -> i32 {
+            let x = x % max;
+
+            if x < 0 {
+                x + max
+            } else {
+                x
+            }
+        }
+
+        Vector {
+            x: clamp_int(within.x, self.x),
+            y: clamp_int(within.y, self.y),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Robot {
+    pub position: Vector,
+    pub velocity: Vector,
+}
+
+pub struct Lobby<'a> {
+    bounds: Vector,
+    robots: &'a [Robot],
+    seconds: i32,
+}
+
+impl<'a> Lobby<'a> {
+    pub fn new(bounds: Vector, robots: &'a [Robot], seconds: i32) -> Self {
+        Lobby {
+            bounds,
+            robots,
+            seconds,
+        }
+    }
+
+    pub fn seconds(&self) -> i32 {
+        self.seconds
+    }
+}
+
+impl fmt::Display for Lobby<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut counts = vec![0; (self.bounds.x * self.bounds.y) as usize];
+
+        for r in self.robots {
+            let position = (r.position + r.velocity * self.seconds).clamp(&self.bounds);
+            counts[(position.x + position.y * self.bounds.x) as usize] += 1;
+        }
+
+        for row in counts.chunks(self.bounds.x as usize) {
+            for count in row {
+                match count {
+                    0 => write!(f, ".")?,
+                    1..=9 => write!(f, "{}", count)?,
+                    _ => write!(f, "+")?,
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Simulation for Lobby<'_> {
+    fn step(&mut self) -> bool {
+        self.seconds += 1;
+
+        true
+    }
+}
+
+pub fn parse_input(input: &str) -> Vec<Robot> {
+    fn parse_vector(vector: &str) -> Vector {
+        let parts: Vec<i32> = vector
+            .split(&[',', '='])
+            .skip(1)
+            .map(|s| s.parse().unwrap())
+            .collect();
+
+        Vector {
+            x: parts[0],
+            y: parts[1],
+        }
+    }
+
+    input
+        .lines()
+        .map(|l| {
+            let (position, velocity) = l.split(" ").collect_tuple().unwrap();
+
+            Robot {
+                position: parse_vector(position),
+                velocity: parse_vector(velocity),
+            }
+        })
+        .collect()
+}
+
+pub fn solve_part_1(bounds: &Vector, robots: &[Robot]) -> i64 {
+    safety_factor(bounds, robots, 100)
+}
+
+/// Product of the robot counts in each quadrant after `seconds`.
+pub fn safety_factor(bounds: &Vector, robots: &[Robot], seconds: i32) -> i64 {
+    let (mx, my): (i32, i32) = (bounds.x / 2, bounds.y / 2);
+    let final_positions = robots
+        .iter()
+        .map(|r| (r.position + r.velocity * seconds).clamp(bounds))
+        .collect::<Vec<Vector>>();
+
+    let (mut ne, mut nw, mut sw, mut se): (i64, i64, i64, i64) = (0, 0, 0, 0);
+
+    for v in final_positions {
+        if v.x < mx {
+            if v.y < my {
+                nw += 1;
+            } else if v.y > my {
+                sw += 1;
+            }
+        } else if v.x > mx {
+            if v.y < my {
+                ne += 1;
+            } else if v.y > my {
+                se += 1;
+            }
+        }
+    }
+
+    ne * nw * sw * se
+}
+
+pub fn solve_part_2(bounds: &Vector, robots: &[Robot]) -> i32 {
+    // Every robot is back where it started after width * height seconds, so nothing new
+    // happens after that.
+    'next_second: for seconds in 1..=bounds.x * bounds.y {
+        let mut positions = vec![false; (bounds.x * bounds.y) as usize];
+
+        for p in robots {
+            let position = (p.position + p.velocity * seconds).clamp(bounds);
+            let i = position.x + position.y * bounds.x;
+
+            if positions[i as usize] {
+                continue 'next_second;
+            } else {
+                positions[i as usize] = true;
+            }
+        }
+
+        return seconds;
+    }
+
+    -1
+}
+
+pub fn robot_image(bounds: &Vector, robots: &[Robot], seconds: i32) -> Image {
+    let points = robots.iter().map(|r| {
+        let position = (r.position + r.velocity * seconds).clamp(bounds);
+
+        (position.x as usize, position.y as usize)
+    });
+
+    Image::from_points(
+        bounds.x as usize,
+        bounds.y as usize,
+        points,
+        BLACK,
+        [0, 200, 0],
+    )
+}
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn solve(&self, part: u8, input: &str, params: &Params) -> Result<String, SolveError> {
+        // Four digit velocities times at most 100000 seconds stay within an i32.
+        check_lines(input, r"^p=\d{1,4},\d{1,4} v=-?\d{1,4},-?\d{1,4}$")?;
+        let robots = parse_input(input);
+        let bounds = Vector {
+            x: param_in(params, "width", 101, 1..=1000)?,
+            y: param_in(params, "height", 103, 1..=1000)?,
+        };
+
+        match part {
+            1 => {
+                let seconds = param_in(params, "seconds", 100, 0..=100_000)?;
+
+                Ok(safety_factor(&bounds, &robots, seconds).to_string())
+            }
+            2 => Ok(solve_part_2(&bounds, &robots).to_string()),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_14::{parse_input, robot_image, solve_part_1, Lobby, Vector};
+    use crate::image::BLACK;
+    use crate::terminal::Simulation;
+
+    const SMALL_EXAMPLE: &str = "p=0,4 v=3,-3\n\
+        p=6,3 v=-1,-3\n\
+        p=10,3 v=-1,2\n\
+        p=2,0 v=2,-1\n\
+        p=0,0 v=1,3\n\
+        p=3,0 v=-2,-2\n\
+        p=7,6 v=-1,-3\n\
+        p=3,0 v=-1,-2\n\
+        p=9,3 v=2,3\n\
+        p=7,3 v=-1,2\n\
+        p=2,4 v=2,-3\n\
+        p=9,5 v=-3,-3\n";
+
+    #[test]
+    fn test_parse_input_on_small_example() {
+        let robots = parse_input(SMALL_EXAMPLE);
+        let first_robot = &robots[0];
+
+        assert_eq!(Vector { x: 0, y: 4 }, first_robot.position);
+        assert_eq!(Vector { x: 3, y: -3 }, first_robot.velocity);
+    }
+
+    #[test]
+    fn test_clamp() {
+        assert_eq!(
+            Vector { x: 9, y: 1 },
+            Vector { x: -1, y: 6 }.clamp(&Vector { x: 10, y: 5 })
+        )
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let robots = parse_input(SMALL_EXAMPLE);
+
+        assert_eq!(12, solve_part_1(&Vector { x: 11, y: 7 }, &robots))
+    }
+
+    #[test]
+    fn test_lobby_display() {
+        let robots = parse_input("p=2,4 v=2,-3\n");
+        let mut lobby = Lobby {
+            bounds: Vector { x: 11, y: 7 },
+            robots: &robots,
+            seconds: 0,
+        };
+
+        assert_eq!(
+            "...........\n\
+            ...........\n\
+            ...........\n\
+            ...........\n\
+            ..1........\n\
+            ...........\n\
+            ...........\n",
+            lobby.to_string()
+        );
+
+        assert!(lobby.step());
+
+        assert_eq!(
+            "...........\n\
+            ....1......\n\
+            ...........\n\
+            ...........\n\
+            ...........\n\
+            ...........\n\
+            ...........\n",
+            lobby.to_string()
+        );
+    }
+
+    #[test]
+    fn test_robot_image() {
+        let robots = parse_input("p=2,4 v=2,-3\n");
+        let image = robot_image(&Vector { x: 11, y: 7 }, &robots, 1);
+
+        assert_eq!(11, image.width());
+        assert_eq!(7, image.height());
+        assert_eq!([0, 200, 0], image.get(4, 1));
+        assert_eq!(BLACK, image.get(2, 4));
+    }
+}
@@ -0,0 +1,500 @@
+//! Day 2, Red-Nosed Reports: which level reports are safe, with and without the Problem Dampener.
+
+use crate::solver::{check_lines, param, Params, SolveError, Solver};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+pub fn parse_input(input: &str) -> Vec<Vec<i32>> {
+    let mut result = vec![];
+
+    for line in input.lines() {
+        let parts = line
+            .split_whitespace()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        result.push(parts);
+    }
+
+    result
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+}
+
+/// Which directions a report may move in: `Either` means one direction throughout, `Mixed`
+/// lets it change.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DirectionRule {
+    Increasing,
+    Decreasing,
+    Either,
+    Mixed,
+}
+
+impl FromStr for DirectionRule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "increasing" => Ok(DirectionRule::Increasing),
+            "decreasing" => Ok(DirectionRule::Decreasing),
+            "either" => Ok(DirectionRule::Either),
+            "mixed" => Ok(DirectionRule::Mixed),
+            _ => Err(format!("unknown direction rule '{}'", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Rules {
+    pub min_step: i32,
+    pub max_step: i32,
+    pub allow_plateaus: bool,
+    pub direction: DirectionRule,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            direction: DirectionRule::Either,
+        }
+    }
+}
+
+impl Rules {
+    /// Reads `min_step`, `max_step`, `plateaus` and `direction`, defaulting to the puzzle's rules.
+    pub fn from_params(params: &Params) -> Result<Self, SolveError> {
+        let default = Rules::default();
+
+        Ok(Rules {
+            min_step: param(params, "min_step", default.min_step)?,
+            max_step: param(params, "max_step", default.max_step)?,
+            allow_plateaus: param(params, "plateaus", default.allow_plateaus)?,
+            direction: param(params, "direction", default.direction)?,
+        })
+    }
+
+    // The directions a dampened report could settle on, None standing for "any".
+    fn directions(&self) -> Vec<Option<Direction>> {
+        match self.direction {
+            DirectionRule::Increasing => vec![Some(Direction::Increasing)],
+            DirectionRule::Decreasing => vec![Some(Direction::Decreasing)],
+            DirectionRule::Either => vec![Some(Direction::Increasing), Some(Direction::Decreasing)],
+            DirectionRule::Mixed => vec![None],
+        }
+    }
+
+    fn check_step(&self, diff: i32, direction: Option<Direction>) -> Result<(), Violation> {
+        if diff == 0 {
+            return match self.allow_plateaus {
+                true => Ok(()),
+                false => Err(Violation::Plateau),
+            };
+        }
+
+        match (direction, diff > 0) {
+            (Some(Direction::Increasing), false) | (Some(Direction::Decreasing), true) => {
+                return Err(match self.direction {
+                    DirectionRule::Either => Violation::DirectionChange,
+                    _ => Violation::WrongDirection,
+                })
+            }
+            _ => {}
+        }
+
+        if diff.abs() < self.min_step {
+            Err(Violation::StepTooSmall)
+        } else if diff.abs() > self.max_step {
+            Err(Violation::StepTooLarge)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Violation {
+    StepTooSmall,
+    StepTooLarge,
+    Plateau,
+    DirectionChange,
+    WrongDirection,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Violation::StepTooSmall => "step too small",
+            Violation::StepTooLarge => "step too large",
+            Violation::Plateau => "level repeated",
+            Violation::DirectionChange => "direction changed",
+            Violation::WrongDirection => "wrong direction",
+        };
+
+        write!(f, "{}", description)
+    }
+}
+
+/// Why a report is or isn't safe: the direction it was taken to be moving in and, if it failed,
+/// the index of the first level that broke a rule.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub direction: Option<Direction>,
+    pub failure: Option<(usize, Violation)>,
+}
+
+impl Diagnostic {
+    pub fn is_safe(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.failure {
+            None => write!(f, "safe")?,
+            Some((index, violation)) => write!(f, "{} at index {}", violation, index)?,
+        }
+
+        match self.direction {
+            Some(Direction::Increasing) => write!(f, ", increasing"),
+            Some(Direction::Decreasing) => write!(f, ", decreasing"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reports with fewer than two levels have no steps that could break a rule, so are safe.
+pub fn diagnose(input: &[i32], rules: &Rules) -> Diagnostic {
+    let direction = match rules.direction {
+        DirectionRule::Increasing => Some(Direction::Increasing),
+        DirectionRule::Decreasing => Some(Direction::Decreasing),
+        DirectionRule::Either => infer_direction(input),
+        DirectionRule::Mixed => None,
+    };
+
+    for index in 1..input.len() {
+        if let Err(violation) = rules.check_step(input[index] - input[index - 1], direction) {
+            return Diagnostic {
+                direction,
+                failure: Some((index, violation)),
+            };
+        }
+    }
+
+    Diagnostic {
+        direction,
+        failure: None,
+    }
+}
+
+/// The direction most steps move in, so a single stray level at the start doesn't decide it.
+/// Ties go to the first step that moves; `None` if no step does.
+pub fn infer_direction(input: &[i32]) -> Option<Direction> {
+    let signs = input
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).signum())
+        .filter(|sign| *sign != 0)
+        .collect::<Vec<i32>>();
+    let balance = signs.iter().sum::<i32>();
+
+    match balance.cmp(&0).then(signs.first().unwrap_or(&0).cmp(&0)) {
+        Ordering::Greater => Some(Direction::Increasing),
+        Ordering::Less => Some(Direction::Decreasing),
+        Ordering::Equal => None,
+    }
+}
+
+/// Finds the fewest levels to remove, at most `max_removals`, for the report to be safe,
+/// returning their indices in order or `None` when more would be needed.
+pub fn dampen(input: &[i32], max_removals: usize) -> Option<Vec<usize>> {
+    dampen_with_rules(input, max_removals, &Rules::default())
+}
+
+pub fn dampen_with_rules(input: &[i32], max_removals: usize, rules: &Rules) -> Option<Vec<usize>> {
+    rules
+        .directions()
+        .into_iter()
+        .filter_map(|direction| dampen_in_direction(input, max_removals, rules, direction))
+        .min_by_key(|removed| removed.len())
+}
+
+// removals[i] is the fewest removals among levels 0..=i that keep level i, with previous[i]
+// the kept level before it. Only the last max_removals + 1 levels can precede i, as anything
+// further back would mean removing too many in between.
+fn dampen_in_direction(
+    input: &[i32],
+    max_removals: usize,
+    rules: &Rules,
+    direction: Option<Direction>,
+) -> Option<Vec<usize>> {
+    if input.is_empty() {
+        return Some(vec![]);
+    }
+
+    let mut removals = (0..input.len()).collect::<Vec<usize>>();
+    let mut previous = vec![None; input.len()];
+
+    for i in 0..input.len() {
+        for j in i.saturating_sub(max_removals + 1)..i {
+            if rules.check_step(input[i] - input[j], direction).is_ok()
+                && removals[j] + (i - j - 1) < removals[i]
+            {
+                removals[i] = removals[j] + (i - j - 1);
+                previous[i] = Some(j);
+            }
+        }
+    }
+
+    let (count, last) = (0..input.len())
+        .map(|i| (removals[i] + (input.len() - 1 - i), i))
+        .min()?;
+    if count > max_removals {
+        return None;
+    }
+
+    let mut kept = vec![false; input.len()];
+    let mut index = Some(last);
+    while let Some(i) = index {
+        kept[i] = true;
+        index = previous[i];
+    }
+
+    Some((0..input.len()).filter(|&i| !kept[i]).collect())
+}
+
+pub fn is_safe(input: &[i32], can_remove: bool) -> bool {
+    dampen(input, if can_remove { 1 } else { 0 }).is_some()
+}
+
+pub fn count_safe(input: &[Vec<i32>], max_removals: usize, rules: &Rules) -> i32 {
+    input
+        .iter()
+        .filter(|report| dampen_with_rules(report, max_removals, rules).is_some())
+        .count() as i32
+}
+
+pub fn solve_part_1(input: &[Vec<i32>]) -> i32 {
+    count_safe(input, 0, &Rules::default())
+}
+
+pub fn solve_part_2(input: &[Vec<i32>]) -> i32 {
+    count_safe(input, 1, &Rules::default())
+}
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn solve(&self, part: u8, input: &str, params: &Params) -> Result<String, SolveError> {
+        check_lines(input, r"^\s*(-?\d{1,9}(\s+-?\d{1,9})*)?\s*$")?;
+        let reports = parse_input(input);
+        let rules = Rules::from_params(params)?;
+
+        match part {
+            1 => Ok(count_safe(&reports, 0, &rules).to_string()),
+            2 => Ok(count_safe(&reports, param(params, "removals", 1)?, &rules).to_string()),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_2::{
+        count_safe, dampen, dampen_with_rules, diagnose, infer_direction, is_safe, parse_input,
+        solve_part_1, solve_part_2, Diagnostic, Direction, DirectionRule, Rules, Violation,
+    };
+    use crate::testing::Rng;
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse_input() {
+        let input = "1 2 3\n4 5 6";
+        let expected = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+        assert_eq!(expected, parse_input(input));
+    }
+
+    #[rstest]
+    #[case(vec![1, 2], false, true)]
+    #[case(vec![1, 3], false, true)]
+    #[case(vec![1, 4], false, true)]
+    #[case(vec![1, 5], false, false)]
+    #[case(vec![1, 1], false, false)]
+    #[case(vec![3, 2], false, true)]
+    #[case(vec![1, 2, 4, 7], false, true)]
+    #[case(vec![1, 2, 4, 7, 20], true, true)]
+    #[case(vec![20, 1, 2, 4, 7], true, true)]
+    #[case(vec![1, 20, 2, 4, 7 ], true, true)]
+    #[case(vec![54, 55, 57, 59, 61], false, true)]
+    #[case(vec![57, 54, 55, 57, 59, 61], true, true)]
+    fn test_is_safe(#[case] input: Vec<i32>, #[case] can_remove: bool, #[case] expected: bool) {
+        assert_eq!(expected, is_safe(&input, can_remove));
+    }
+
+    #[test]
+    fn test_failing_case() {
+        let input = vec![57, 54, 55, 57, 59, 61];
+        assert!(is_safe(&input, true));
+    }
+
+    #[rstest]
+    #[case(vec![7, 6, 4, 2, 1], 0, Some(vec![]))]
+    #[case(vec![1, 2, 7, 8, 9], 1, None)]
+    #[case(vec![1, 3, 2, 4, 5], 1, Some(vec![2]))]
+    #[case(vec![8, 6, 4, 4, 1], 1, Some(vec![3]))]
+    #[case(vec![1, 2, 9, 3, 20, 4], 1, None)]
+    #[case(vec![1, 2, 9, 3, 20, 4], 2, Some(vec![2, 4]))]
+    #[case(vec![9, 1, 2, 3, 20, 30], 3, Some(vec![0, 4, 5]))]
+    #[case(vec![5, 1], 2, Some(vec![1]))]
+    #[case(vec![], 0, Some(vec![]))]
+    #[case(vec![7], 0, Some(vec![]))]
+    #[case(vec![7, 7], 0, None)]
+    #[case(vec![7, 7], 1, Some(vec![1]))]
+    fn test_dampen(
+        #[case] input: Vec<i32>,
+        #[case] max_removals: usize,
+        #[case] expected: Option<Vec<usize>>,
+    ) {
+        assert_eq!(expected, dampen(&input, max_removals));
+    }
+
+    #[test]
+    fn test_solve_example() {
+        let input =
+            parse_input("7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n");
+
+        assert_eq!(2, solve_part_1(&input));
+        assert_eq!(4, solve_part_2(&input));
+        assert_eq!(6, count_safe(&input, 2, &Rules::default()));
+    }
+
+    #[rstest]
+    #[case(vec![7, 6, 4, 2, 1], None)]
+    #[case(vec![1, 2, 7, 8, 9], Some((2, Violation::StepTooLarge)))]
+    #[case(vec![1, 3, 2, 4, 5], Some((2, Violation::DirectionChange)))]
+    #[case(vec![8, 6, 4, 4, 1], Some((3, Violation::Plateau)))]
+    #[case(vec![], None)]
+    #[case(vec![7], None)]
+    #[case(vec![7, 9], None)]
+    #[case(vec![7, 7], Some((1, Violation::Plateau)))]
+    #[case(vec![5, 1, 2, 3, 4], Some((1, Violation::DirectionChange)))]
+    fn test_diagnose(#[case] input: Vec<i32>, #[case] failure: Option<(usize, Violation)>) {
+        assert_eq!(failure, diagnose(&input, &Rules::default()).failure);
+    }
+
+    #[test]
+    fn test_diagnose_reports_direction() {
+        assert_eq!(
+            Diagnostic {
+                direction: Some(Direction::Decreasing),
+                failure: Some((3, Violation::Plateau)),
+            },
+            diagnose(&[8, 6, 4, 4, 1], &Rules::default())
+        );
+        assert_eq!(
+            "level repeated at index 3, decreasing",
+            diagnose(&[8, 6, 4, 4, 1], &Rules::default()).to_string()
+        );
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let rules = Rules {
+            min_step: 0,
+            max_step: 5,
+            allow_plateaus: true,
+            direction: DirectionRule::Increasing,
+        };
+
+        assert!(diagnose(&[1, 1, 6, 8], &rules).is_safe());
+        assert_eq!(
+            Some((1, Violation::WrongDirection)),
+            diagnose(&[8, 6], &rules).failure
+        );
+        assert_eq!(Some(vec![1]), dampen_with_rules(&[1, 0, 6, 8], 1, &rules));
+
+        let mixed = Rules {
+            direction: DirectionRule::Mixed,
+            ..Rules::default()
+        };
+
+        assert!(diagnose(&[1, 3, 2, 4, 5], &mixed).is_safe());
+        assert_eq!(
+            Some((2, Violation::StepTooLarge)),
+            diagnose(&[1, 3, 9], &mixed).failure
+        );
+    }
+
+    #[rstest]
+    #[case(vec![], None)]
+    #[case(vec![4, 4], None)]
+    #[case(vec![9, 1, 2, 3], Some(Direction::Increasing))]
+    #[case(vec![1, 2, 1], Some(Direction::Increasing))]
+    #[case(vec![3, 2, 1, 2], Some(Direction::Decreasing))]
+    fn test_infer_direction(#[case] input: Vec<i32>, #[case] expected: Option<Direction>) {
+        assert_eq!(expected, infer_direction(&input));
+    }
+
+    fn brute_force_removals(input: &[i32], max_removals: usize, rules: &Rules) -> Option<usize> {
+        (0..=max_removals.min(input.len())).find(|&count| {
+            (0..input.len()).combinations(count).any(|removed| {
+                let kept = (0..input.len())
+                    .filter(|i| !removed.contains(i))
+                    .map(|i| input[i])
+                    .collect_vec();
+
+                diagnose(&kept, rules).is_safe()
+            })
+        })
+    }
+
+    fn generated_reports(count: usize) -> Vec<Vec<i32>> {
+        let mut rng = Rng::new(0x2545f4914f6cdd1d);
+
+        (0..count)
+            .map(|_| {
+                let length = rng.below(9) as usize;
+                (0..length).map(|_| rng.below(12) as i32).collect()
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case(Rules::default())]
+    #[case(Rules { allow_plateaus: true, ..Rules::default() })]
+    #[case(Rules { direction: DirectionRule::Mixed, ..Rules::default() })]
+    #[case(Rules { min_step: 2, max_step: 5, direction: DirectionRule::Decreasing, ..Rules::default() })]
+    fn test_dampen_matches_brute_force(#[case] rules: Rules) {
+        for report in generated_reports(500) {
+            for max_removals in 0..=3 {
+                let removed = dampen_with_rules(&report, max_removals, &rules);
+                let expected = brute_force_removals(&report, max_removals, &rules);
+
+                assert_eq!(expected, removed.as_ref().map(|r| r.len()), "{:?}", report);
+
+                if let Some(removed) = removed {
+                    let kept = (0..report.len())
+                        .filter(|i| !removed.contains(i))
+                        .map(|i| report[i])
+                        .collect_vec();
+
+                    assert!(diagnose(&kept, &rules).is_safe(), "{:?}", report);
+                }
+            }
+        }
+    }
+}
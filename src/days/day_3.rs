@@ -0,0 +1,733 @@
+//! Day 3, Mull It Over: summing `mul` instructions in corrupted memory.
+
+use crate::solver::{param, Params, SolveError, Solver};
+use crate::terminal::{paint, Colour};
+use itertools::Itertools;
+use std::io::{self, Read};
+use std::iter::{once, zip};
+use std::ops::RangeInclusive;
+
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// What running an instruction does: add a value to the total, or change whether values
+/// are currently counted. Values and totals wrap rather than overflow.
+#[derive(Debug, Clone, Copy)]
+pub enum Effect {
+    Value(fn(&[i64]) -> i64),
+    Enable,
+    Disable,
+    Toggle,
+}
+
+/// An entry in the instruction table, read as `name(a,b,...)` with `arity` operands of one
+/// to `max_digits` digits each.
+#[derive(Debug, Clone)]
+pub struct Operation {
+    pub name: String,
+    pub arity: RangeInclusive<usize>,
+    pub max_digits: usize,
+    pub effect: Effect,
+}
+
+impl Operation {
+    pub fn new(name: &str, arity: RangeInclusive<usize>, effect: Effect) -> Self {
+        Operation {
+            name: name.to_string(),
+            arity,
+            max_digits: 3,
+            effect,
+        }
+    }
+
+    pub fn with_max_digits(mut self, max_digits: usize) -> Self {
+        self.max_digits = max_digits;
+        self
+    }
+
+    // Reads this operation at the start of memory. `NeedMore` means the memory ran out before
+    // it could tell, so a streaming reader should fetch more and try again.
+    fn read(&self, memory: &[u8]) -> Match<Vec<i64>> {
+        let mut length = self.name.len() + 1;
+        let opening = self.name.bytes().chain([b'(']);
+        if !opening
+            .zip(memory)
+            .all(|(expected, actual)| expected == *actual)
+        {
+            return Match::Absent;
+        }
+
+        let mut operands = vec![];
+        match memory.get(length) {
+            None => return Match::NeedMore,
+            Some(b')') => length += 1,
+            Some(_) => loop {
+                let digits = memory[length..]
+                    .iter()
+                    .take(self.max_digits.saturating_add(1))
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+                if length + digits == memory.len() && digits <= self.max_digits {
+                    return Match::NeedMore;
+                }
+                if !(1..=self.max_digits).contains(&digits) {
+                    return Match::Absent;
+                }
+
+                let Some(operand) = parse_number(&memory[length..length + digits]) else {
+                    return Match::Absent;
+                };
+                operands.push(operand);
+                length += digits + 1;
+
+                match memory[length - 1] {
+                    b',' if operands.len() < *self.arity.end() => continue,
+                    b')' => break,
+                    _ => return Match::Absent,
+                }
+            },
+        }
+
+        match self.arity.contains(&operands.len()) {
+            true => Match::Found(operands, length),
+            false => Match::Absent,
+        }
+    }
+}
+
+enum Match<T> {
+    Found(T, usize),
+    NeedMore,
+    Absent,
+}
+
+fn parse_number(digits: &[u8]) -> Option<i64> {
+    digits.iter().try_fold(0_i64, |n, digit| {
+        n.checked_mul(10)?.checked_add((digit - b'0') as i64)
+    })
+}
+
+/// The instructions the tokenizer recognises; `standard` is the puzzle's `mul`, `do` and
+/// `don't`, and more can be added with `with`.
+#[derive(Debug, Clone)]
+pub struct InstructionSet {
+    operations: Vec<Operation>,
+}
+
+impl InstructionSet {
+    pub fn standard() -> Self {
+        InstructionSet { operations: vec![] }
+            .with(Operation::new(
+                "mul",
+                2..=2,
+                Effect::Value(|operands| operands.iter().fold(1, |a, &b| a.wrapping_mul(b))),
+            ))
+            .with(Operation::new("do", 0..=0, Effect::Enable))
+            .with(Operation::new("don't", 0..=0, Effect::Disable))
+    }
+
+    /// Adds an operation, replacing any existing one with the same name.
+    pub fn with(mut self, operation: Operation) -> Self {
+        self.operations.retain(|o| o.name != operation.name);
+        self.operations.push(operation);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Operation> {
+        self.operations.iter().find(|o| o.name == name)
+    }
+
+    /// The value an instruction produces, whether or not it would be counted.
+    pub fn evaluate(&self, instruction: &Instruction) -> Option<i64> {
+        match self.get(instruction.name)?.effect {
+            Effect::Value(evaluate) => Some(evaluate(&instruction.operands)),
+            _ => None,
+        }
+    }
+
+    // The first operation, in table order, found at the start of memory. An earlier operation
+    // that needs more memory to decide blocks later ones, unless the memory is `complete`.
+    fn read(&self, memory: &[u8], complete: bool) -> Match<Instruction<'_>> {
+        for operation in &self.operations {
+            match operation.read(memory) {
+                Match::Found(operands, length) => {
+                    let instruction = Instruction {
+                        name: &operation.name,
+                        operands,
+                    };
+
+                    return Match::Found(instruction, length);
+                }
+                Match::NeedMore if !complete => return Match::NeedMore,
+                _ => {}
+            }
+        }
+
+        Match::Absent
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Instruction<'a> {
+    pub name: &'a str,
+    pub operands: Vec<i64>,
+}
+
+/// An instruction found in the memory, with the byte range it was read from.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Token<'a> {
+    pub offset: usize,
+    pub length: usize,
+    pub instruction: Instruction<'a>,
+}
+
+/// Scans corrupted memory for instructions, skipping anything that isn't one exactly.
+pub struct Tokenizer<'a> {
+    instructions: &'a InstructionSet,
+    memory: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(instructions: &'a InstructionSet, memory: &'a str) -> Self {
+        Tokenizer {
+            instructions,
+            memory: memory.as_bytes(),
+            offset: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        while self.offset < self.memory.len() {
+            let offset = self.offset;
+
+            if let Match::Found(instruction, length) =
+                self.instructions.read(&self.memory[offset..], true)
+            {
+                self.offset += length;
+
+                return Some(Token {
+                    offset,
+                    length,
+                    instruction,
+                });
+            }
+
+            self.offset += 1;
+        }
+
+        None
+    }
+}
+
+/// Reads memory in chunks of `chunk_size` bytes, finding the same tokens as `Tokenizer` would
+/// on the whole memory. Only the current chunk and any instruction still being read across a
+/// chunk boundary are held at once. A `chunk_size` of 0 is reported as an `InvalidInput` error.
+pub struct Scanner<'a, R: Read> {
+    instructions: &'a InstructionSet,
+    reader: R,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    position: usize,
+    offset: usize,
+    finished: bool,
+}
+
+impl<'a, R: Read> Scanner<'a, R> {
+    pub fn new(instructions: &'a InstructionSet, reader: R, chunk_size: usize) -> Self {
+        Scanner {
+            instructions,
+            reader,
+            chunk_size,
+            buffer: vec![],
+            position: 0,
+            offset: 0,
+            finished: false,
+        }
+    }
+
+    // Drops what has been scanned and appends the next chunk, noting when the reader runs dry.
+    fn fill(&mut self) -> io::Result<()> {
+        if self.chunk_size == 0 {
+            self.finished = true;
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunks need at least one byte",
+            ));
+        }
+
+        self.buffer.drain(..self.position);
+        self.offset += self.position;
+        self.position = 0;
+
+        let end = self.buffer.len();
+        self.buffer.resize(end + self.chunk_size, 0);
+        let count = loop {
+            match self.reader.read(&mut self.buffer[end..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.buffer.truncate(end);
+                    return Err(e);
+                }
+                Ok(count) => break count,
+            }
+        };
+        self.buffer.truncate(end + count);
+        self.finished = count == 0;
+
+        Ok(())
+    }
+}
+
+impl<'a, R: Read> Iterator for Scanner<'a, R> {
+    type Item = io::Result<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let remaining = &self.buffer[self.position..];
+
+            let result = match remaining.is_empty() {
+                true if self.finished => return None,
+                true => Match::NeedMore,
+                false => self.instructions.read(remaining, self.finished),
+            };
+
+            match result {
+                Match::Found(instruction, length) => {
+                    let offset = self.offset + self.position;
+                    self.position += length;
+
+                    return Some(Ok(Token {
+                        offset,
+                        length,
+                        instruction,
+                    }));
+                }
+                Match::NeedMore => {
+                    if let Err(e) = self.fill() {
+                        return Some(Err(e));
+                    }
+                }
+                Match::Absent => self.position += 1,
+            }
+        }
+    }
+}
+
+/// Runs instructions, keeping the running total and whether values are currently counted.
+/// With `conditionals` off, enabling and disabling instructions are ignored as in part 1.
+#[derive(Debug, Clone)]
+pub struct Interpreter<'a> {
+    instructions: &'a InstructionSet,
+    pub conditionals: bool,
+    pub enabled: bool,
+    pub total: i64,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(instructions: &'a InstructionSet, conditionals: bool) -> Self {
+        Interpreter {
+            instructions,
+            conditionals,
+            enabled: true,
+            total: 0,
+        }
+    }
+
+    /// Returns the value added to the total, if the instruction produced one that counted.
+    pub fn execute(&mut self, instruction: &Instruction) -> Option<i64> {
+        let operation = self.instructions.get(instruction.name)?;
+
+        match operation.effect {
+            Effect::Value(evaluate) if self.enabled => {
+                let value = evaluate(&instruction.operands);
+                self.total = self.total.wrapping_add(value);
+                return Some(value);
+            }
+            Effect::Enable if self.conditionals => self.enabled = true,
+            Effect::Disable if self.conditionals => self.enabled = false,
+            Effect::Toggle if self.conditionals => self.enabled = !self.enabled,
+            _ => {}
+        }
+
+        None
+    }
+
+    pub fn run(mut self, memory: &str) -> Self {
+        for token in Tokenizer::new(self.instructions, memory) {
+            self.execute(&token.instruction);
+        }
+
+        self
+    }
+}
+
+/// An instruction as the part 2 interpreter saw it: its value, if it has one, and whether
+/// values were being counted once it had run.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Traced<'a> {
+    pub token: Token<'a>,
+    pub value: Option<i64>,
+    pub enabled: bool,
+}
+
+impl Traced<'_> {
+    pub fn counted(&self) -> bool {
+        self.enabled && self.value.is_some()
+    }
+}
+
+pub fn trace<R: Read>(
+    instructions: &InstructionSet,
+    reader: R,
+    chunk_size: usize,
+) -> io::Result<Vec<Traced<'_>>> {
+    let mut interpreter = Interpreter::new(instructions, true);
+
+    Scanner::new(instructions, reader, chunk_size)
+        .map(|token| {
+            let token = token?;
+            interpreter.execute(&token.instruction);
+
+            Ok(Traced {
+                value: instructions.evaluate(&token.instruction),
+                enabled: interpreter.enabled,
+                token,
+            })
+        })
+        .collect()
+}
+
+/// The memory with counted values in green, skipped ones in grey, and instructions that
+/// enable or disable counting in cyan and magenta.
+pub fn annotate(memory: &str, trace: &[Traced]) -> String {
+    let mut result = String::with_capacity(memory.len());
+    let mut position = 0;
+
+    for traced in trace {
+        let Token { offset, length, .. } = traced.token;
+        let colour = match (traced.value, traced.enabled) {
+            (Some(_), true) => Colour::Green,
+            (Some(_), false) => Colour::Grey,
+            (None, true) => Colour::Cyan,
+            (None, false) => Colour::Magenta,
+        };
+
+        result.push_str(&memory[position..offset]);
+        result.push_str(&paint(&memory[offset..offset + length], colour));
+        position = offset + length;
+    }
+
+    result.push_str(&memory[position..]);
+    result
+}
+
+pub fn table(trace: &[Traced]) -> String {
+    let rows = trace
+        .iter()
+        .map(|traced| {
+            let instruction = &traced.token.instruction;
+
+            [
+                traced.token.offset.to_string(),
+                instruction.name.to_string(),
+                instruction.operands.iter().join(","),
+                traced.value.map_or("-".to_string(), |v| v.to_string()),
+                if traced.enabled { "yes" } else { "no" }.to_string(),
+            ]
+        })
+        .collect_vec();
+    let header = ["offset", "instruction", "operands", "value", "enabled"].map(String::from);
+    let widths = (0..header.len())
+        .map(|column| {
+            once(&header)
+                .chain(&rows)
+                .map(|row| row[column].len())
+                .max()
+                .unwrap()
+        })
+        .collect_vec();
+
+    let mut result = String::new();
+    for row in once(&header).chain(&rows) {
+        let line = zip(row, &widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .join("  ");
+
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    let total = trace
+        .iter()
+        .filter(|t| t.counted())
+        .filter_map(|t| t.value)
+        .fold(0_i64, i64::wrapping_add);
+    result.push_str(&format!("total: {}\n", total));
+    result
+}
+
+pub fn solve_part_1(input: &str) -> i64 {
+    Interpreter::new(&InstructionSet::standard(), false)
+        .run(input)
+        .total
+}
+
+pub fn solve_part_2(input: &str) -> i64 {
+    Interpreter::new(&InstructionSet::standard(), true)
+        .run(input)
+        .total
+}
+
+/// Both parts from a single pass over a reader.
+pub fn solve_stream<R: Read>(reader: R, chunk_size: usize) -> io::Result<(i64, i64)> {
+    let instructions = InstructionSet::standard();
+    let mut part_1 = Interpreter::new(&instructions, false);
+    let mut part_2 = Interpreter::new(&instructions, true);
+
+    for token in Scanner::new(&instructions, reader, chunk_size) {
+        let instruction = token?.instruction;
+        part_1.execute(&instruction);
+        part_2.execute(&instruction);
+    }
+
+    Ok((part_1.total, part_2.total))
+}
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn solve(&self, part: u8, input: &str, params: &Params) -> Result<String, SolveError> {
+        let standard = InstructionSet::standard();
+        let mul = standard.get("mul").unwrap().clone();
+        let instructions = standard.with(mul.with_max_digits(param(params, "max_digits", 3)?));
+
+        match part {
+            1 | 2 => Ok(Interpreter::new(&instructions, part == 2)
+                .run(input)
+                .total
+                .to_string()),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_3::{
+        annotate, solve_part_1, solve_part_2, solve_stream, table, trace, Day3, Effect,
+        Instruction, InstructionSet, Interpreter, Operation, Scanner, Token, Tokenizer,
+    };
+    use crate::solver::Solver;
+    use maplit::hashmap;
+    use rstest::rstest;
+    use std::io;
+
+    #[rstest]
+    #[case("", 0)]
+    #[case("mul(1,2)", 2)]
+    #[case("mul(123,2345)", 0)]
+    #[case("mul(1234,5)mul(12,3)", 36)]
+    #[case("mul ( 2 , 4 )mul(2,)", 0)]
+    #[case(
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))",
+        161
+    )]
+    fn test_solve_part_1(#[case] input: &str, #[case] expected: i64) {
+        assert_eq!(solve_part_1(input), expected);
+    }
+
+    #[rstest]
+    #[case("", 0)]
+    #[case(
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))",
+        48
+    )]
+    fn test_solve_part_2(#[case] input: &str, #[case] expected: i64) {
+        assert_eq!(solve_part_2(input), expected);
+    }
+
+    #[test]
+    fn test_tokenizer() {
+        let instructions = InstructionSet::standard();
+        let tokens =
+            Tokenizer::new(&instructions, "xmul(2,4)&don't()_mul(5,5do()").collect::<Vec<Token>>();
+
+        assert_eq!(
+            vec![
+                Token {
+                    offset: 1,
+                    length: 8,
+                    instruction: Instruction {
+                        name: "mul",
+                        operands: vec![2, 4]
+                    }
+                },
+                Token {
+                    offset: 10,
+                    length: 7,
+                    instruction: Instruction {
+                        name: "don't",
+                        operands: vec![]
+                    }
+                },
+                Token {
+                    offset: 25,
+                    length: 4,
+                    instruction: Instruction {
+                        name: "do",
+                        operands: vec![]
+                    }
+                },
+            ],
+            tokens
+        );
+    }
+
+    #[test]
+    fn test_interpreter_state() {
+        let instructions = InstructionSet::standard();
+        let mut interpreter = Interpreter::new(&instructions, true);
+        let instruction = |name, operands| Instruction { name, operands };
+
+        assert_eq!(
+            Some(8),
+            interpreter.execute(&instruction("mul", vec![2, 4]))
+        );
+        assert_eq!(None, interpreter.execute(&instruction("don't", vec![])));
+        assert!(!interpreter.enabled);
+        assert_eq!(None, interpreter.execute(&instruction("mul", vec![5, 5])));
+        assert_eq!(8, interpreter.total);
+    }
+
+    #[rstest]
+    #[case("add(1,2)sub(10,4)", 9)]
+    #[case("mul(2,3,4)add(1)", 24)]
+    #[case("flip()add(1,1)flip()add(2,2)mul(1,2,3,4,5,6)", 724)]
+    #[case("mul(123456,2)add(123456,1)", 123457)]
+    fn test_custom_instructions(#[case] memory: &str, #[case] expected: i64) {
+        let instructions = InstructionSet::standard()
+            .with(Operation::new("add", 2..=2, Effect::Value(|o| o[0] + o[1])).with_max_digits(6))
+            .with(Operation::new("sub", 2..=2, Effect::Value(|o| o[0] - o[1])))
+            .with(Operation::new(
+                "mul",
+                2..=usize::MAX,
+                Effect::Value(|o| o.iter().product()),
+            ))
+            .with(Operation::new("flip", 0..=0, Effect::Toggle));
+
+        assert_eq!(
+            expected,
+            Interpreter::new(&instructions, true).run(memory).total
+        );
+    }
+
+    #[test]
+    fn test_solver_digit_limit() {
+        let params = hashmap! {"max_digits".to_string() => "4".to_string()};
+
+        assert_eq!(
+            Ok("2468".to_string()),
+            Day3.solve(1, "mul(1234,2)", &params)
+        );
+    }
+
+    #[test]
+    fn test_solver_wraps_on_overflow() {
+        let params = hashmap! {"max_digits".to_string() => usize::MAX.to_string()};
+
+        assert_eq!(
+            Ok(i64::MIN.to_string()),
+            Day3.solve(1, "mul(4611686018427387904,2)", &params)
+        );
+        assert_eq!(
+            Ok((i64::MIN + 1).to_string()),
+            Day3.solve(1, "mul(4611686018427387904,2)mul(1,1)", &params)
+        );
+    }
+
+    const EXAMPLE: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[rstest]
+    #[case(1)]
+    #[case(2)]
+    #[case(3)]
+    #[case(7)]
+    #[case(1024)]
+    fn test_solve_stream(#[case] chunk_size: usize) {
+        assert_eq!(
+            (161, 48),
+            solve_stream(EXAMPLE.as_bytes(), chunk_size).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_solve_stream_rejects_empty_chunks() {
+        let error = solve_stream("mul(2,3)".as_bytes(), 0).unwrap_err();
+
+        assert_eq!(io::ErrorKind::InvalidInput, error.kind());
+        assert_eq!(
+            1,
+            Scanner::new(&InstructionSet::standard(), "mul(2,3)".as_bytes(), 0).count()
+        );
+    }
+
+    #[test]
+    fn test_scanner_matches_tokenizer() {
+        let instructions = InstructionSet::standard()
+            .with(Operation::new(
+                "mul",
+                2..=usize::MAX,
+                Effect::Value(|o| o.iter().product()),
+            ))
+            .with(Operation::new("do", 0..=1, Effect::Enable));
+        let memory = "mul(1,2,3)do(4)mulmul(5,6do()mul(123,4567,8)mul(7,8)";
+        let expected = Tokenizer::new(&instructions, memory).collect::<Vec<Token>>();
+
+        for chunk_size in 1..=memory.len() {
+            let scanned = Scanner::new(&instructions, memory.as_bytes(), chunk_size)
+                .collect::<Result<Vec<Token>, _>>()
+                .unwrap();
+
+            assert_eq!(expected, scanned, "chunk size {}", chunk_size);
+        }
+    }
+
+    #[test]
+    fn test_annotate() {
+        let memory = "mul(2,4)don't()mul(5,5)?do()";
+        let instructions = InstructionSet::standard();
+        let trace = trace(&instructions, memory.as_bytes(), 4).unwrap();
+
+        assert_eq!(
+            "\x1b[32mmul(2,4)\x1b[0m\x1b[35mdon't()\x1b[0m\x1b[90mmul(5,5)\x1b[0m?\x1b[36mdo()\x1b[0m",
+            annotate(memory, &trace)
+        );
+    }
+
+    #[test]
+    fn test_table() {
+        let instructions = InstructionSet::standard();
+        let trace = trace(&instructions, EXAMPLE.as_bytes(), 16).unwrap();
+
+        assert_eq!(
+            "offset  instruction  operands  value  enabled\n\
+             1       mul          2,4       8      yes\n\
+             20      don't                  -      no\n\
+             28      mul          5,5       25     no\n\
+             48      mul          11,8      88     no\n\
+             59      do                     -      yes\n\
+             64      mul          8,5       40     yes\n\
+             total: 48\n",
+            table(&trace)
+        );
+    }
+}
@@ -0,0 +1,674 @@
+//! Day 4, Ceres Search: counting XMAS and X-MAS in a letter grid.
+
+use crate::solver::{Params, SolveError, Solver};
+use itertools::Itertools;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+pub fn parse_input(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+/// The letters held row by row in one buffer so any cell can be looked up directly. Letters
+/// are taken as bytes, so words and templates are expected to be ASCII.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Grid {
+    letters: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    pub fn new(lines: &[&str]) -> Result<Self, String> {
+        let width = lines.first().map_or(0, |line| line.len());
+
+        if let Some(row) = lines.iter().position(|line| line.len() != width) {
+            return Err(format!(
+                "row {} has {} letters, expected {}",
+                row,
+                lines[row].len(),
+                width
+            ));
+        }
+
+        Ok(Grid {
+            letters: lines.iter().flat_map(|line| line.bytes()).collect(),
+            width,
+            height: lines.len(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The letter at a cell, or `None` outside the grid.
+    pub fn get(&self, row: i32, column: i32) -> Option<u8> {
+        if row < 0 || column < 0 || row as usize >= self.height || column as usize >= self.width {
+            return None;
+        }
+
+        Some(self.letters[row as usize * self.width + column as usize])
+    }
+
+    // Every line of cells running in a direction, each starting from the edge of the grid.
+    fn lines(&self, direction: Direction) -> impl Iterator<Item = Vec<(i32, i32)>> + '_ {
+        let inside = |row: i32, column: i32| self.get(row, column).is_some();
+
+        (0..self.height as i32)
+            .cartesian_product(0..self.width as i32)
+            .filter(move |&(row, column)| !inside(row - direction.row, column - direction.column))
+            .map(move |(row, column)| {
+                (0..)
+                    .map(|i| (row + i * direction.row, column + i * direction.column))
+                    .take_while(|&(row, column)| inside(row, column))
+                    .collect()
+            })
+    }
+}
+
+/// A step between neighbouring letters, in rows and columns.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Direction {
+    pub row: i32,
+    pub column: i32,
+}
+
+impl Direction {
+    pub const RIGHT: Direction = Direction { row: 0, column: 1 };
+    pub const DOWN: Direction = Direction { row: 1, column: 0 };
+    pub const DOWN_RIGHT: Direction = Direction { row: 1, column: 1 };
+    pub const DOWN_LEFT: Direction = Direction { row: 1, column: -1 };
+
+    pub fn reversed(&self) -> Direction {
+        Direction {
+            row: -self.row,
+            column: -self.column,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match (self.row, self.column) {
+            (0, 1) => "right",
+            (0, -1) => "left",
+            (1, 0) => "down",
+            (-1, 0) => "up",
+            (1, 1) => "down-right",
+            (1, -1) => "down-left",
+            (-1, 1) => "up-right",
+            (-1, -1) => "up-left",
+            _ => "other",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Directions {
+    Orthogonal,
+    Diagonal,
+    Both,
+}
+
+impl FromStr for Directions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "orthogonal" => Ok(Directions::Orthogonal),
+            "diagonal" => Ok(Directions::Diagonal),
+            "both" => Ok(Directions::Both),
+            _ => Err(format!("unknown directions '{}'", s)),
+        }
+    }
+}
+
+/// Which ways words may run; `reversed` adds right-to-left, upwards and so on.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Search {
+    pub directions: Directions,
+    pub reversed: bool,
+}
+
+impl Search {
+    pub fn steps(&self) -> Vec<Direction> {
+        let forwards = match self.directions {
+            Directions::Orthogonal => vec![Direction::RIGHT, Direction::DOWN],
+            Directions::Diagonal => vec![Direction::DOWN_RIGHT, Direction::DOWN_LEFT],
+            Directions::Both => vec![
+                Direction::RIGHT,
+                Direction::DOWN,
+                Direction::DOWN_RIGHT,
+                Direction::DOWN_LEFT,
+            ],
+        };
+
+        match self.reversed {
+            true => forwards.iter().flat_map(|d| [*d, d.reversed()]).collect(),
+            false => forwards,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Match<'a> {
+    pub word: &'a str,
+    pub row: usize,
+    pub column: usize,
+    pub direction: Direction,
+}
+
+/// An Aho-Corasick automaton that reports every word ending at each letter fed to it, so all
+/// the words are found in a single pass along a line.
+struct WordMachine {
+    children: Vec<HashMap<u8, usize>>,
+    fail: Vec<usize>,
+    outputs: Vec<Vec<usize>>,
+}
+
+impl WordMachine {
+    fn new(words: &[&str]) -> Self {
+        let mut machine = WordMachine {
+            children: vec![HashMap::new()],
+            fail: vec![0],
+            outputs: vec![vec![]],
+        };
+
+        for (index, word) in words.iter().enumerate().filter(|(_, w)| !w.is_empty()) {
+            let mut state = 0;
+            for letter in word.bytes() {
+                state = match machine.children[state].get(&letter) {
+                    Some(&next) => next,
+                    None => {
+                        machine.children.push(HashMap::new());
+                        machine.fail.push(0);
+                        machine.outputs.push(vec![]);
+                        let next = machine.children.len() - 1;
+                        machine.children[state].insert(letter, next);
+                        next
+                    }
+                };
+            }
+            machine.outputs[state].push(index);
+        }
+
+        let mut queue = machine.children[0]
+            .values()
+            .copied()
+            .collect::<VecDeque<_>>();
+        while let Some(state) = queue.pop_front() {
+            for (letter, next) in machine.children[state].clone() {
+                let mut fallback = machine.fail[state];
+                while fallback != 0 && !machine.children[fallback].contains_key(&letter) {
+                    fallback = machine.fail[fallback];
+                }
+
+                machine.fail[next] = match machine.children[fallback].get(&letter) {
+                    Some(&target) if target != next => target,
+                    _ => 0,
+                };
+                let inherited = machine.outputs[machine.fail[next]].clone();
+                machine.outputs[next].extend(inherited);
+                queue.push_back(next);
+            }
+        }
+
+        machine
+    }
+
+    fn advance(&self, mut state: usize, letter: u8) -> usize {
+        loop {
+            if let Some(&next) = self.children[state].get(&letter) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.fail[state];
+        }
+    }
+}
+
+/// Every place one of the words can be read, by its first letter and the way it runs. A word
+/// that reads the same backwards is found once from each end when `reversed` is set.
+pub fn find_words<'a>(grid: &Grid, words: &[&'a str], search: &Search) -> Vec<Match<'a>> {
+    let machine = WordMachine::new(words);
+    let steps = search.steps();
+    let mut found = vec![];
+
+    for (step, direction) in steps.iter().enumerate() {
+        for line in grid.lines(*direction) {
+            let mut state = 0;
+
+            for (index, &(row, column)) in line.iter().enumerate() {
+                state = machine.advance(state, grid.get(row, column).unwrap());
+
+                for &word in &machine.outputs[state] {
+                    let (row, column) = line[index + 1 - words[word].len()];
+                    found.push((row as usize, column as usize, word, step));
+                }
+            }
+        }
+    }
+
+    found
+        .into_iter()
+        .sorted()
+        .map(|(row, column, word, step)| Match {
+            word: words[word],
+            row,
+            column,
+            direction: steps[step],
+        })
+        .collect()
+}
+
+pub fn solve_part_1(lines: &[&str]) -> i32 {
+    let search = Search {
+        directions: Directions::Both,
+        reversed: true,
+    };
+
+    find_words(&Grid::new(lines).unwrap(), &["XMAS"], &search).len() as i32
+}
+
+/// A block of letters to find in the grid, where `.` matches any letter. Rows are written on
+/// separate lines or separated by `/`, as in `M.S / .A. / M.S`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Template {
+    rows: Vec<Vec<Option<u8>>>,
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .split(['\n', '/'])
+            .map(|row| row.trim())
+            .filter(|row| !row.is_empty())
+            .map(|row| row.bytes().map(|c| (c != b'.').then_some(c)).collect_vec())
+            .collect_vec();
+
+        if rows.is_empty() || rows.iter().any(|row| row.len() != rows[0].len()) {
+            return Err(format!("'{}' is not a rectangular template", s));
+        }
+
+        Ok(Template { rows })
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rows = self.rows.iter().map(|row| {
+            row.iter()
+                .map(|c| c.unwrap_or(b'.') as char)
+                .collect::<String>()
+        });
+
+        write!(f, "{}", rows.format(" / "))
+    }
+}
+
+impl Template {
+    /// Turned a quarter turn clockwise.
+    pub fn rotated(&self) -> Template {
+        let height = self.rows.len();
+
+        Template {
+            rows: (0..self.rows[0].len())
+                .map(|column| {
+                    (0..height)
+                        .map(|row| self.rows[height - 1 - row][column])
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Mirrored left to right.
+    pub fn reflected(&self) -> Template {
+        Template {
+            rows: self
+                .rows
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        }
+    }
+
+    /// The distinct templates reachable by rotating and reflecting, starting with this one.
+    pub fn variants(&self) -> Vec<Template> {
+        let mut variants: Vec<Template> = vec![];
+        let mut rotation = self.clone();
+
+        for _ in 0..4 {
+            for variant in [rotation.clone(), rotation.reflected()] {
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+            }
+            rotation = rotation.rotated();
+        }
+
+        variants
+    }
+
+    fn matches_at(&self, grid: &Grid, row: i32, column: i32) -> bool {
+        self.rows.iter().enumerate().all(|(r, letters)| {
+            letters.iter().enumerate().all(|(c, letter)| match letter {
+                Some(letter) => grid.get(row + r as i32, column + c as i32) == Some(*letter),
+                None => true,
+            })
+        })
+    }
+}
+
+/// Where a variant of a template matched, by its top left corner and its index in `variants`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct TemplateMatch {
+    pub row: usize,
+    pub column: usize,
+    pub variant: usize,
+}
+
+pub fn find_template(grid: &Grid, template: &Template) -> Vec<TemplateMatch> {
+    let variants = template.variants();
+    let mut result = vec![];
+
+    for row in 0..grid.height() {
+        for column in 0..grid.width() {
+            for (variant, candidate) in variants.iter().enumerate() {
+                if candidate.matches_at(grid, row as i32, column as i32) {
+                    result.push(TemplateMatch {
+                        row,
+                        column,
+                        variant,
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+pub fn solve_part_2(lines: &[&str]) -> i32 {
+    let x_mas = "M.S / .A. / M.S".parse().unwrap();
+
+    find_template(&Grid::new(lines).unwrap(), &x_mas).len() as i32
+}
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn solve(&self, part: u8, input: &str, _params: &Params) -> Result<String, SolveError> {
+        let lines = parse_input(input);
+        Grid::new(&lines).map_err(|_| SolveError::InvalidInput)?;
+
+        match part {
+            1 => Ok(solve_part_1(&lines).to_string()),
+            2 => Ok(solve_part_2(&lines).to_string()),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_4::{
+        find_template, find_words, parse_input, solve_part_1, solve_part_2, Direction, Directions,
+        Grid, Match, Search, Template, TemplateMatch,
+    };
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    const EXAMPLE: [&str; 10] = [
+        "MMMSXXMASM",
+        "MSAMXMSMSA",
+        "AMXSXMAAMM",
+        "MSAMASMSMX",
+        "XMASAMXAMM",
+        "XXAMMXXAMA",
+        "SMSMSASXSS",
+        "SAXAMASAAA",
+        "MAMMMXMMMM",
+        "MXMXAXMASX",
+    ];
+
+    // Reads the word letter by letter from a cell, the reference the automaton is checked against.
+    fn word_at(grid: &Grid, word: &str, row: i32, column: i32, direction: Direction) -> bool {
+        word.bytes().enumerate().all(|(i, expected)| {
+            let row = row + i as i32 * direction.row;
+            let column = column + i as i32 * direction.column;
+
+            grid.get(row, column) == Some(expected)
+        })
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let input = "123\n456";
+        let expected = vec!["123", "456"];
+
+        assert_eq!(expected, parse_input(input));
+    }
+
+    #[rstest]
+    #[case(vec!["XMAS"], 0, 0, Direction::RIGHT, true)]
+    #[case(vec!["SAMX"], 0, 3, Direction::RIGHT.reversed(), true)]
+    #[case(vec!["XMA"], 0, 0, Direction::RIGHT, false)]
+    #[case(vec!["X...", ".M..", "..A.", "...S"], 0, 0, Direction::DOWN_RIGHT, true)]
+    fn test_find_words_in_direction(
+        #[case] input: Vec<&str>,
+        #[case] row: usize,
+        #[case] column: usize,
+        #[case] direction: Direction,
+        #[case] expected: bool,
+    ) {
+        let grid = Grid::new(&input).unwrap();
+        let search = Search {
+            directions: Directions::Both,
+            reversed: true,
+        };
+        let found = Match {
+            word: "XMAS",
+            row,
+            column,
+            direction,
+        };
+
+        assert_eq!(
+            expected,
+            find_words(&grid, &["XMAS"], &search).contains(&found)
+        );
+    }
+
+    #[test]
+    fn test_find_words() {
+        let input = Grid::new(&["CAT.", "A...", "TAC."]).unwrap();
+        let search = Search {
+            directions: Directions::Orthogonal,
+            reversed: true,
+        };
+
+        assert_eq!(
+            vec![
+                Match {
+                    word: "CAT",
+                    row: 0,
+                    column: 0,
+                    direction: Direction::RIGHT
+                },
+                Match {
+                    word: "CAT",
+                    row: 0,
+                    column: 0,
+                    direction: Direction::DOWN
+                },
+                Match {
+                    word: "CAT",
+                    row: 2,
+                    column: 2,
+                    direction: Direction::RIGHT.reversed()
+                },
+            ],
+            find_words(&input, &["CAT"], &search)
+        );
+
+        let forwards = Search {
+            reversed: false,
+            ..search
+        };
+        assert_eq!(2, find_words(&input, &["CAT"], &forwards).len());
+        assert_eq!(3, find_words(&input, &["CAT", "TAC"], &forwards).len());
+    }
+
+    #[rstest]
+    #[case(vec!["XMAS"])]
+    #[case(vec!["MAS", "AS", "S", "SAMX", "XMASAMX"])]
+    #[case(vec!["MM", "MMM", "MMMM", "M"])]
+    fn test_find_words_matches_brute_force(#[case] words: Vec<&str>) {
+        let grid = Grid::new(&EXAMPLE).unwrap();
+        let search = Search {
+            directions: Directions::Both,
+            reversed: true,
+        };
+        let steps = search.steps();
+
+        let mut expected = vec![];
+        for (row, column) in (0..10).cartesian_product(0..10) {
+            for word in &words {
+                for direction in &steps {
+                    if word_at(&grid, word, row, column, *direction) {
+                        expected.push((row as usize, column as usize, *word, *direction));
+                    }
+                }
+            }
+        }
+
+        let found = find_words(&grid, &words, &search)
+            .iter()
+            .map(|m| (m.row, m.column, m.word, m.direction))
+            .collect_vec();
+
+        assert_eq!(expected, found);
+    }
+
+    #[test]
+    fn test_rectangular_grid() {
+        let input = ["XMASX", "....M", "....A", "....S"];
+
+        assert_eq!(2, solve_part_1(&input));
+        assert_eq!(1, solve_part_1(&["X", "M", "A", "S", "."]));
+        assert!(Grid::new(&["XMAS", "X"]).is_err());
+        assert_eq!(None, Grid::new(&input).unwrap().get(0, 5));
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let input = vec![
+            "MMMSXXMASM",
+            "MSAMXMSMSA",
+            "AMXSXMAAMM",
+            "MSAMASMSMX",
+            "XMASAMXAMM",
+            "XXAMMXXAMA",
+            "SMSMSASXSS",
+            "SAXAMASAAA",
+            "MAMMMXMMMM",
+            "MXMXAXMASX",
+        ];
+
+        assert_eq!(18, solve_part_1(&input));
+    }
+
+    #[rstest]
+    #[case(vec!["MAS"], "MAS", 0, 0, true)]
+    #[case(vec!["SAM"], "MAS", 0, 0, true)]
+    #[case(vec!["MXX", "XAX", "XXS"], "M.. / .A. / ..S", 0, 0, true)]
+    #[case(vec!["XXS", "XAX", "MXX"], "M.. / .A. / ..S", 0, 0, true)]
+    #[case(vec!["XXS", "XAX", "MXX"], "M.. / .A. / ..S", 0, 1, false)]
+    fn test_template_matches_at(
+        #[case] input: Vec<&str>,
+        #[case] template: &str,
+        #[case] row: i32,
+        #[case] column: i32,
+        #[case] expected: bool,
+    ) {
+        let template: Template = template.parse().unwrap();
+
+        let grid = Grid::new(&input).unwrap();
+
+        assert_eq!(
+            expected,
+            template
+                .variants()
+                .iter()
+                .any(|variant| variant.matches_at(&grid, row, column))
+        );
+    }
+
+    #[test]
+    fn test_template_variants() {
+        let x_mas: Template = "M.S\n.A.\nM.S".parse().unwrap();
+
+        assert_eq!(
+            vec![
+                "M.S / .A. / M.S",
+                "S.M / .A. / S.M",
+                "M.M / .A. / S.S",
+                "S.S / .A. / M.M"
+            ],
+            x_mas
+                .variants()
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+        );
+        assert_eq!(8, "AB / C.".parse::<Template>().unwrap().variants().len());
+        assert!("AB / C".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn test_find_template() {
+        let input = Grid::new(&["M.S.", ".A..", "M.S."]).unwrap();
+        let x_mas = "M.S / .A. / M.S".parse().unwrap();
+
+        assert_eq!(
+            vec![TemplateMatch {
+                row: 0,
+                column: 0,
+                variant: 0
+            }],
+            find_template(&input, &x_mas)
+        );
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let input = vec![
+            "MMMSXXMASM",
+            "MSAMXMSMSA",
+            "AMXSXMAAMM",
+            "MSAMASMSMX",
+            "XMASAMXAMM",
+            "XXAMMXXAMA",
+            "SMSMSASXSS",
+            "SAXAMASAAA",
+            "MAMMMXMMMM",
+            "MXMXAXMASX",
+        ];
+
+        assert_eq!(9, solve_part_2(&input));
+    }
+}
@@ -0,0 +1,831 @@
+//! Day 5, Print Queue: checking and repairing update orders against page rules.
+
+use crate::days::day_5::CheckResults::{Correct, ErrorIndices};
+use crate::solver::{check_lines, Params, SolveError, Solver};
+use itertools::Itertools;
+use maplit::hashset;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::fmt::Formatter;
+
+#[derive(Debug, PartialEq)]
+pub enum CheckResults {
+    Correct(i32),
+    ErrorIndices(usize, usize),
+}
+
+pub fn parse_input(input: &str) -> (HashMap<i32, HashSet<i32>>, Vec<Vec<i32>>) {
+    let mut rules: HashMap<i32, HashSet<i32>> = HashMap::new();
+    let mut pages = vec![];
+
+    for (before, after) in parse_rules(input) {
+        rules.entry(before).or_insert(hashset![]).insert(after);
+    }
+
+    let parts = input.split("\n\n").collect::<Vec<&str>>();
+    for line in parts[1].lines() {
+        let values = line.split(",").map(|i| i.parse().unwrap()).collect();
+        pages.push(values);
+    }
+
+    (rules, pages)
+}
+
+/// The `before|after` rules in the order they are listed, duplicates included.
+pub fn parse_rules(input: &str) -> Vec<(i32, i32)> {
+    let rules_part = input.split("\n\n").next().unwrap();
+
+    rules_part
+        .lines()
+        .map(|line| {
+            let parts = line
+                .split("|")
+                .map(|p| p.parse().unwrap())
+                .collect::<Vec<i32>>();
+
+            (parts[0], parts[1])
+        })
+        .collect()
+}
+
+pub fn order_check(rules: &HashMap<i32, HashSet<i32>>, pages: &[i32]) -> CheckResults {
+    let mut seen = HashMap::<i32, usize>::new();
+
+    for (i, page) in pages.iter().enumerate() {
+        if let Some(afters) = rules.get(page) {
+            for after in afters {
+                if let Some(index) = seen.get(after) {
+                    return ErrorIndices(*index, i);
+                }
+            }
+        }
+
+        seen.insert(*page, i);
+    }
+
+    Correct(pages[pages.len() / 2])
+}
+
+pub fn solve_part_1(rules: &HashMap<i32, HashSet<i32>>, pages: &[Vec<i32>]) -> i32 {
+    let mut result = 0;
+
+    for page in pages {
+        match order_check(rules, page) {
+            Correct(middle) => {
+                result += middle;
+            }
+            ErrorIndices(_, _) => {}
+        }
+    }
+
+    result
+}
+
+/// Sorts an update by the rules between its own pages, keeping pages the rules don't order in
+/// their original relative order. `None` if those rules form a cycle.
+pub fn reorder(rules: &HashMap<i32, HashSet<i32>>, pages: &[i32]) -> Option<Vec<i32>> {
+    let afters = update_graph(rules, pages);
+
+    let mut befores = vec![0; pages.len()];
+    for index in afters.iter().flatten() {
+        befores[*index] += 1;
+    }
+
+    let mut ready = (0..pages.len())
+        .filter(|i| befores[*i] == 0)
+        .map(Reverse)
+        .collect::<BinaryHeap<Reverse<usize>>>();
+    let mut result = Vec::with_capacity(pages.len());
+
+    while let Some(Reverse(index)) = ready.pop() {
+        result.push(pages[index]);
+
+        for after in &afters[index] {
+            befores[*after] -= 1;
+            if befores[*after] == 0 {
+                ready.push(Reverse(*after));
+            }
+        }
+    }
+
+    (result.len() == pages.len()).then_some(result)
+}
+
+// For each page of an update, the positions of the pages the rules say must follow it.
+fn update_graph(rules: &HashMap<i32, HashSet<i32>>, pages: &[i32]) -> Vec<Vec<usize>> {
+    let positions = pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| (*page, i))
+        .collect::<HashMap<i32, usize>>();
+
+    pages
+        .iter()
+        .map(|page| {
+            rules
+                .get(page)
+                .into_iter()
+                .flatten()
+                .filter_map(|after| positions.get(after).copied())
+                .sorted()
+                .collect()
+        })
+        .collect()
+}
+
+/// A rule `before|after` broken by `after` appearing earlier in the update than `before`.
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    pub before: i32,
+    pub after: i32,
+    pub before_index: usize,
+    pub after_index: usize,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {} comes after {} at {}, breaking {}|{}",
+            self.before, self.before_index, self.after, self.after_index, self.before, self.after
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OrderReport {
+    pub pages: Vec<i32>,
+    pub violations: Vec<Violation>,
+    /// The fewest pages that have to be taken out and put back elsewhere to correct the
+    /// update, `None` if its rules cycle and there is no correct order.
+    pub moves: Option<usize>,
+    pub middle: i32,
+    pub corrected_middle: Option<i32>,
+}
+
+impl OrderReport {
+    pub fn is_correct(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl fmt::Display for OrderReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} broken, ",
+            self.pages.iter().join(","),
+            self.violations.len()
+        )?;
+        match (self.moves, self.corrected_middle) {
+            (Some(moves), Some(corrected)) => writeln!(
+                f,
+                "{} to move, middle {} -> {}",
+                moves, self.middle, corrected
+            )?,
+            _ => writeln!(f, "no correct order, middle {}", self.middle)?,
+        }
+
+        for violation in &self.violations {
+            writeln!(f, "  {}", violation)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Every rule the update breaks, rather than just the first like `order_check`, and what it
+/// takes to correct it.
+pub fn order_report(rules: &HashMap<i32, HashSet<i32>>, pages: &[i32]) -> OrderReport {
+    let afters = update_graph(rules, pages);
+
+    let violations = afters
+        .iter()
+        .enumerate()
+        .flat_map(|(before_index, indices)| {
+            indices
+                .iter()
+                .filter(move |after_index| **after_index < before_index)
+                .map(move |after_index| Violation {
+                    before: pages[before_index],
+                    after: pages[*after_index],
+                    before_index,
+                    after_index: *after_index,
+                })
+        })
+        .collect();
+    let corrected = reorder(rules, pages);
+
+    OrderReport {
+        pages: pages.to_vec(),
+        violations,
+        moves: corrected.as_ref().map(|_| minimum_moves(&afters)),
+        middle: pages[pages.len() / 2],
+        corrected_middle: corrected.map(|c| c[c.len() / 2]),
+    }
+}
+
+// Pages left in place must not have any page that has to precede them, directly or through
+// other rules, after them. Calling a pair of pages where that is broken "inverted", the inversions
+// form a partial order, so the most pages that can stay is its largest antichain, and by
+// Dilworth's theorem the pages to move is the size of a maximum matching over inverted pairs.
+fn minimum_moves(afters: &[Vec<usize>]) -> usize {
+    let mut reachable = vec![vec![false; afters.len()]; afters.len()];
+    for (start, reached) in reachable.iter_mut().enumerate() {
+        let mut stack = vec![start];
+        while let Some(index) = stack.pop() {
+            for after in &afters[index] {
+                if !reached[*after] {
+                    reached[*after] = true;
+                    stack.push(*after);
+                }
+            }
+        }
+    }
+
+    let inverted = (0..afters.len())
+        .map(|i| (i + 1..afters.len()).filter(|j| reachable[*j][i]).collect())
+        .collect::<Vec<Vec<usize>>>();
+
+    fn augment(
+        inverted: &[Vec<usize>],
+        index: usize,
+        visited: &mut [bool],
+        matched: &mut [Option<usize>],
+    ) -> bool {
+        for later in &inverted[index] {
+            if !visited[*later] {
+                visited[*later] = true;
+                if matched[*later].is_none_or(|other| augment(inverted, other, visited, matched)) {
+                    matched[*later] = Some(index);
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    let mut matched = vec![None; afters.len()];
+    (0..afters.len())
+        .filter(|index| {
+            augment(
+                &inverted,
+                *index,
+                &mut vec![false; afters.len()],
+                &mut matched,
+            )
+        })
+        .count()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RuleError {
+    DuplicateRule {
+        before: i32,
+        after: i32,
+        count: usize,
+    },
+    /// An update whose own pages' rules cycle, so it has no correct order.
+    Unorderable { update: usize, cycle: Vec<i32> },
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::DuplicateRule {
+                before,
+                after,
+                count,
+            } => write!(f, "rule {}|{} is listed {} times", before, after, count),
+            RuleError::Unorderable { update, cycle } => write!(
+                f,
+                "update {} cannot be ordered: {}",
+                update + 1,
+                cycle.iter().join(" -> ")
+            ),
+        }
+    }
+}
+
+/// Checks the rules for duplicates, and each update for being orderable at all. Only the rules
+/// between an update's own pages matter, so a cycle across the whole rule set isn't reported.
+pub fn validate(rules: &[(i32, i32)], pages: &[Vec<i32>]) -> Vec<RuleError> {
+    let mut errors = rules
+        .iter()
+        .counts()
+        .into_iter()
+        .filter(|(_, count)| *count > 1)
+        .sorted()
+        .map(|((before, after), count)| RuleError::DuplicateRule {
+            before: *before,
+            after: *after,
+            count,
+        })
+        .collect_vec();
+
+    let mut graph = BTreeMap::<i32, BTreeSet<i32>>::new();
+    for (before, after) in rules {
+        graph.entry(*before).or_default().insert(*after);
+    }
+
+    for (update, update_pages) in pages.iter().enumerate() {
+        let included = update_pages.iter().collect::<HashSet<&i32>>();
+        let induced = graph
+            .iter()
+            .filter(|(before, _)| included.contains(before))
+            .map(|(before, afters)| {
+                let afters = afters.iter().filter(|a| included.contains(a)).copied();
+
+                (*before, afters.collect())
+            })
+            .collect();
+
+        if let Some(cycle) = find_cycle(&induced) {
+            errors.push(RuleError::Unorderable { update, cycle });
+        }
+    }
+
+    errors
+}
+
+// Depth first search from each page in turn, returning the first cycle met, if any.
+fn find_cycle(graph: &BTreeMap<i32, BTreeSet<i32>>) -> Option<Vec<i32>> {
+    fn visit(
+        graph: &BTreeMap<i32, BTreeSet<i32>>,
+        page: i32,
+        on_path: &mut HashMap<i32, bool>,
+        path: &mut Vec<i32>,
+    ) -> Option<Vec<i32>> {
+        on_path.insert(page, true);
+        path.push(page);
+
+        for next in graph.get(&page).into_iter().flatten() {
+            match on_path.get(next) {
+                Some(true) => {
+                    let start = path.iter().position(|p| p == next).unwrap();
+                    return Some(path[start..].iter().chain([next]).copied().collect());
+                }
+                Some(false) => {}
+                None => {
+                    if let Some(cycle) = visit(graph, *next, on_path, path) {
+                        return Some(cycle);
+                    }
+                }
+            }
+        }
+
+        path.pop();
+        on_path.insert(page, false);
+        None
+    }
+
+    let mut on_path = HashMap::new();
+    graph.keys().find_map(|page| {
+        (!on_path.contains_key(page))
+            .then(|| visit(graph, *page, &mut on_path, &mut vec![]))
+            .flatten()
+    })
+}
+
+/// Sums the middle pages of the corrected updates, failing on any update that can't be ordered.
+pub fn solve_part_2(
+    rules: &HashMap<i32, HashSet<i32>>,
+    pages: &[Vec<i32>],
+) -> Result<i32, RuleError> {
+    let mut result = 0;
+
+    for (update, update_pages) in pages.iter().enumerate() {
+        if matches!(order_check(rules, update_pages), Correct(_)) {
+            continue;
+        }
+
+        let Some(ordered) = reorder(rules, update_pages) else {
+            let rule_list = rules
+                .iter()
+                .flat_map(|(before, afters)| afters.iter().map(|after| (*before, *after)))
+                .collect_vec();
+            let cycle = validate(&rule_list, std::slice::from_ref(update_pages))
+                .into_iter()
+                .find_map(|e| match e {
+                    RuleError::Unorderable { cycle, .. } => Some(cycle),
+                    _ => None,
+                })
+                .unwrap_or_default();
+
+            return Err(RuleError::Unorderable { update, cycle });
+        };
+
+        result += ordered[ordered.len() / 2];
+    }
+
+    Ok(result)
+}
+
+/// The rules as a Graphviz digraph, nodes and edges sorted. `pages` restricts it to one
+/// update's pages, and rules broken by the `highlight` update are drawn in red.
+pub fn to_dot(
+    rules: &HashMap<i32, HashSet<i32>>,
+    pages: Option<&[i32]>,
+    highlight: Option<&[i32]>,
+) -> String {
+    let included = |page: &i32| pages.is_none_or(|pages| pages.contains(page));
+    let positions = highlight
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(i, page)| (*page, i))
+        .collect::<HashMap<i32, usize>>();
+
+    let edges = rules
+        .iter()
+        .flat_map(|(before, afters)| afters.iter().map(move |after| (*before, *after)))
+        .filter(|(before, after)| included(before) && included(after))
+        .sorted()
+        .collect_vec();
+    let nodes = edges
+        .iter()
+        .flat_map(|(before, after)| [*before, *after])
+        .chain(pages.unwrap_or_default().iter().copied())
+        .sorted()
+        .dedup();
+
+    let mut dot = String::from("digraph rules {\n");
+    for node in nodes {
+        dot.push_str(&format!("    {};\n", node));
+    }
+    for (before, after) in edges {
+        let violated = matches!(
+            (positions.get(&before), positions.get(&after)),
+            (Some(b), Some(a)) if a < b
+        );
+        let style = if violated { " [color=red]" } else { "" };
+
+        dot.push_str(&format!("    {} -> {}{};\n", before, after, style));
+    }
+    dot.push_str("}\n");
+
+    dot
+}
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn solve(&self, part: u8, input: &str, _params: &Params) -> Result<String, SolveError> {
+        let (rules_part, updates_part) =
+            input.split_once("\n\n").ok_or(SolveError::InvalidInput)?;
+        check_lines(rules_part, r"^\d{1,9}\|\d{1,9}$")?;
+        check_lines(updates_part, r"^\d{1,9}(,\d{1,9})*$")?;
+        let (rules, pages) = parse_input(input);
+
+        match part {
+            1 => Ok(solve_part_1(&rules, &pages).to_string()),
+            2 => solve_part_2(&rules, &pages)
+                .map(|sum| sum.to_string())
+                .map_err(|_| SolveError::InvalidInput),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_5::{
+        order_check, order_report, parse_input, parse_rules, reorder, solve_part_1, solve_part_2,
+        to_dot, validate, CheckResults, RuleError, Violation,
+    };
+    use crate::testing::Rng;
+    use itertools::Itertools;
+    use maplit::{hashmap, hashset};
+    use rstest::rstest;
+    use std::collections::{HashMap, HashSet};
+
+    const EXAMPLE_INPUT: &str = "47|53\n\
+        97|13\n\
+        97|61\n\
+        97|47\n\
+        75|29\n\
+        61|13\n\
+        75|53\n\
+        29|13\n\
+        97|29\n\
+        53|29\n\
+        61|53\n\
+        97|53\n\
+        61|29\n\
+        47|13\n\
+        75|47\n\
+        97|75\n\
+        47|61\n\
+        75|61\n\
+        47|29\n\
+        75|13\n\
+        53|13\n\
+        \n\
+        75,47,61,53,29\n\
+        97,61,53,29,13\n\
+        75,29,13\n\
+        75,97,47,61,53\n\
+        61,13,29\n\
+        97,13,75,29,47\n";
+
+    #[test]
+    fn test_parse_input() {
+        let input = "1|2\n\
+            3|2\n\
+            3|4\n\
+            \n\
+            1,2,3\n\
+            4,5,6\n";
+        let expected_rules = hashmap! {
+            1 => hashset![2],
+            3 => hashset![2, 4],
+        };
+        let expected_pages = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+        let (rules, pages) = parse_input(input);
+
+        assert_eq!(expected_rules, rules);
+        assert_eq!(expected_pages, pages);
+    }
+
+    #[rstest]
+    #[case(hashmap![2=>hashset![3]], vec![1, 2, 3], CheckResults::Correct(2))]
+    #[case(hashmap![3=>hashset![1]], vec![1, 2, 3], CheckResults::ErrorIndices(0, 2))]
+    #[case(hashmap![], vec![1, 2, 3], CheckResults::Correct(2))]
+    fn test_order_check(
+        #[case] rules: HashMap<i32, HashSet<i32>>,
+        #[case] pages: Vec<i32>,
+        #[case] expected: CheckResults,
+    ) {
+        assert_eq!(expected, order_check(&rules, &pages));
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let (rules, pages) = parse_input(EXAMPLE_INPUT);
+        assert_eq!(143, solve_part_1(&rules, &pages));
+    }
+
+    #[test]
+    fn test_solve_part_2() {
+        let (rules, pages) = parse_input(EXAMPLE_INPUT);
+        assert_eq!(Ok(123), solve_part_2(&rules, &pages));
+    }
+
+    // The original repair: swap the first pair found breaking a rule until none do. Slow, but
+    // simple enough to check `reorder` against.
+    fn reorder_by_swaps(rules: &HashMap<i32, HashSet<i32>>, pages: &[i32]) -> Vec<i32> {
+        let mut candidate = pages.to_vec();
+
+        while let CheckResults::ErrorIndices(index_1, index_2) = order_check(rules, &candidate) {
+            candidate.swap(index_1, index_2);
+        }
+
+        candidate
+    }
+
+    #[rstest]
+    #[case(hashmap![], vec![3, 1, 2], vec![3, 1, 2])]
+    #[case(hashmap![2 => hashset![3]], vec![3, 1, 2], vec![1, 2, 3])]
+    #[case(hashmap![1 => hashset![2], 2 => hashset![3]], vec![3, 2, 1], vec![1, 2, 3])]
+    #[case(hashmap![1 => hashset![9]], vec![3, 1], vec![3, 1])]
+    fn test_reorder(
+        #[case] rules: HashMap<i32, HashSet<i32>>,
+        #[case] pages: Vec<i32>,
+        #[case] expected: Vec<i32>,
+    ) {
+        assert_eq!(Some(expected), reorder(&rules, &pages));
+    }
+
+    #[test]
+    fn test_reorder_cycle() {
+        let rules = hashmap! {1 => hashset![2], 2 => hashset![3], 3 => hashset![1]};
+
+        assert_eq!(None, reorder(&rules, &[1, 2, 3]));
+        assert_eq!(Some(vec![1, 2]), reorder(&rules, &[2, 1]));
+    }
+
+    #[test]
+    fn test_reorder_matches_swaps() {
+        let (rules, pages) = parse_input(EXAMPLE_INPUT);
+        for update in &pages {
+            assert_eq!(
+                Some(reorder_by_swaps(&rules, update)),
+                reorder(&rules, update)
+            );
+        }
+
+        // Random updates drawn from a hidden order of 30 pages, with a rule for every pair.
+        let mut rng = Rng::new(0x9e3779b97f4a7c15);
+        let mut order = (10..40).collect::<Vec<i32>>();
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.below(i as u64 + 1) as usize);
+        }
+
+        let mut rules = HashMap::<i32, HashSet<i32>>::new();
+        for (i, before) in order.iter().enumerate() {
+            rules.insert(*before, order[i + 1..].iter().copied().collect());
+        }
+
+        for _ in 0..200 {
+            let mut update = order.clone();
+            for i in (1..update.len()).rev() {
+                update.swap(i, rng.below(i as u64 + 1) as usize);
+            }
+            update.truncate(1 + rng.below(update.len() as u64) as usize);
+
+            assert_eq!(
+                Some(reorder_by_swaps(&rules, &update)),
+                reorder(&rules, &update)
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_example() {
+        let (_, pages) = parse_input(EXAMPLE_INPUT);
+
+        assert_eq!(
+            Vec::<RuleError>::new(),
+            validate(&parse_rules(EXAMPLE_INPUT), &pages)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let input = "1|2\n2|3\n3|1\n1|2\n4|5\n\n1,2,3\n2,1\n5,4\n";
+        let (_, pages) = parse_input(input);
+
+        assert_eq!(
+            vec![
+                RuleError::DuplicateRule {
+                    before: 1,
+                    after: 2,
+                    count: 2
+                },
+                RuleError::Unorderable {
+                    update: 0,
+                    cycle: vec![1, 2, 3, 1]
+                },
+            ],
+            validate(&parse_rules(input), &pages)
+        );
+        assert_eq!(
+            "update 1 cannot be ordered: 1 -> 2 -> 3 -> 1",
+            validate(&parse_rules(input), &pages)[1].to_string()
+        );
+    }
+
+    #[test]
+    fn test_validate_ignores_global_cycle() {
+        let input = "1|2\n2|3\n3|1\n\n1,2\n2,3\n3,1\n";
+        let (_, pages) = parse_input(input);
+
+        assert_eq!(
+            Vec::<RuleError>::new(),
+            validate(&parse_rules(input), &pages)
+        );
+    }
+
+    #[test]
+    fn test_solve_part_2_with_cycle() {
+        let (rules, pages) = parse_input("1|2\n2|3\n3|1\n\n1,2\n3,2,1\n");
+
+        assert_eq!(
+            Err(RuleError::Unorderable {
+                update: 1,
+                cycle: vec![1, 2, 3, 1]
+            }),
+            solve_part_2(&rules, &pages)
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let (rules, _) = parse_input("3|1\n1|2\n2|4\n\n1,2\n");
+
+        assert_eq!(
+            "digraph rules {\n    1;\n    2;\n    3;\n    4;\n    \
+                1 -> 2;\n    2 -> 4;\n    3 -> 1;\n}\n",
+            to_dot(&rules, None, None)
+        );
+    }
+
+    #[test]
+    fn test_to_dot_restricted_and_highlighted() {
+        let (rules, pages) = parse_input(EXAMPLE_INPUT);
+
+        assert_eq!(
+            "digraph rules {\n    13;\n    29;\n    61;\n    \
+                29 -> 13 [color=red];\n    61 -> 13;\n    61 -> 29;\n}\n",
+            to_dot(&rules, Some(&pages[4]), Some(&pages[4]))
+        );
+    }
+
+    #[test]
+    fn test_order_report() {
+        let (rules, pages) = parse_input(EXAMPLE_INPUT);
+        let report = order_report(&rules, &pages[5]);
+
+        assert_eq!(
+            vec![
+                (75, 13, 2, 1),
+                (29, 13, 3, 1),
+                (47, 13, 4, 1),
+                (47, 29, 4, 3)
+            ],
+            report
+                .violations
+                .iter()
+                .map(|v| (v.before, v.after, v.before_index, v.after_index))
+                .collect_vec()
+        );
+        assert_eq!(Some(2), report.moves);
+        assert_eq!((75, Some(47)), (report.middle, report.corrected_middle));
+        assert_eq!(
+            "97,13,75,29,47: 4 broken, 2 to move, middle 75 -> 47\n  \
+                75 at 2 comes after 13 at 1, breaking 75|13\n  \
+                29 at 3 comes after 13 at 1, breaking 29|13\n  \
+                47 at 4 comes after 13 at 1, breaking 47|13\n  \
+                47 at 4 comes after 29 at 3, breaking 47|29\n",
+            report.to_string()
+        );
+        assert!(order_report(&rules, &pages[0]).is_correct());
+    }
+
+    #[test]
+    fn test_order_report_cycle() {
+        let rules = hashmap! {1 => hashset![2], 2 => hashset![3], 3 => hashset![1]};
+        let report = order_report(&rules, &[1, 2, 3]);
+
+        assert_eq!(
+            vec![Violation {
+                before: 3,
+                after: 1,
+                before_index: 2,
+                after_index: 0
+            }],
+            report.violations
+        );
+        assert_eq!((None, None), (report.moves, report.corrected_middle));
+    }
+
+    fn longest_common_subsequence(a: &[i32], b: &[i32]) -> usize {
+        let mut lengths = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for i in 0..a.len() {
+            for j in 0..b.len() {
+                lengths[i + 1][j + 1] = match a[i] == b[j] {
+                    true => lengths[i][j] + 1,
+                    false => lengths[i][j + 1].max(lengths[i + 1][j]),
+                };
+            }
+        }
+
+        lengths[a.len()][b.len()]
+    }
+
+    #[test]
+    fn test_order_report_moves_match_brute_force() {
+        let mut rng = Rng::new(0x2545f4914f6cdd1d);
+
+        // Sparse rules only ever point from lower to higher pages, so they order the pages
+        // partially but never cycle.
+        for _ in 0..300 {
+            let mut update = (1..=9).collect_vec();
+            for i in (1..update.len()).rev() {
+                update.swap(i, rng.below(i as u64 + 1) as usize);
+            }
+            update.truncate(rng.below(6) as usize + 1);
+
+            let mut rules = HashMap::<i32, HashSet<i32>>::new();
+            for (before, after) in update.iter().tuple_combinations() {
+                if before < after && rng.below(2) == 0 {
+                    rules.entry(*before).or_default().insert(*after);
+                }
+            }
+
+            // Moving pages keeps the rest in order, so the fewest moves to reach a target order
+            // leave their longest common subsequence alone.
+            let expected = update
+                .iter()
+                .copied()
+                .permutations(update.len())
+                .filter(|p| matches!(order_check(&rules, p), CheckResults::Correct(_)))
+                .map(|p| update.len() - longest_common_subsequence(&update, &p))
+                .min();
+
+            assert_eq!(
+                expected,
+                order_report(&rules, &update).moves,
+                "{:?}",
+                update
+            );
+        }
+    }
+}
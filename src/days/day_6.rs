@@ -0,0 +1,805 @@
+//! Day 6, Guard Gallivant: the guard's patrol route and obstacles that trap it in a loop.
+
+use crate::image::{Image, BLACK};
+use crate::solver::{check_grid, param, Params, SolveError, Solver};
+use crate::terminal::Simulation;
+use itertools::Itertools;
+use maplit::{hashmap, hashset};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Formatter;
+use std::thread;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Content {
+    Empty,
+    Object,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Location {
+    pub row: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Action {
+    Moved,
+    Turned,
+}
+
+/// Where the guard is and which way it faces after taking a step.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Step {
+    pub location: Location,
+    pub direction: Direction,
+    pub action: Action,
+}
+
+pub fn parse_input(input: &str) -> (Vec<Vec<Content>>, Location) {
+    let mut location = Location {
+        row: usize::MAX,
+        column: usize::MAX,
+    };
+
+    let map = input
+        .lines()
+        .enumerate()
+        .map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(column, c)| match c {
+                    '.' => Content::Empty,
+                    '#' => Content::Object,
+                    '^' => {
+                        location.row = row;
+                        location.column = column;
+                        Content::Empty
+                    }
+                    _ => panic!("unexpected character: {}", c),
+                })
+                .collect()
+        })
+        .collect();
+
+    (map, location)
+}
+
+/// The guard's patrol one step at a time. It ends when the guard walks off the map or starts
+/// repeating itself; `by_ref()` takes a few steps at a time.
+pub struct GuardSim<'a> {
+    map: &'a [Vec<Content>],
+    location: Location,
+    direction: Direction,
+    path: Vec<Location>,
+    visited: HashSet<Location>,
+    states: HashSet<(Location, Direction)>,
+    looped: bool,
+}
+
+impl<'a> GuardSim<'a> {
+    pub fn new(map: &'a [Vec<Content>], start_location: &Location) -> Self {
+        GuardSim {
+            map,
+            location: start_location.clone(),
+            direction: Direction::North,
+            path: vec![start_location.clone()],
+            visited: hashset![start_location.clone()],
+            states: hashset![(start_location.clone(), Direction::North)],
+            looped: false,
+        }
+    }
+
+    pub fn location(&self) -> &Location {
+        &self.location
+    }
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Every location the guard has stood on so far in order, starting with where it began.
+    pub fn path(&self) -> &[Location] {
+        &self.path
+    }
+
+    pub fn visited(&self) -> &HashSet<Location> {
+        &self.visited
+    }
+
+    pub fn looped(&self) -> bool {
+        self.looped
+    }
+}
+
+impl Iterator for GuardSim<'_> {
+    type Item = Step;
+
+    fn next(&mut self) -> Option<Step> {
+        if self.looped {
+            return None;
+        }
+
+        let ahead = ahead(self.map, &self.location, &self.direction)?;
+        let action = match self.map[ahead.row][ahead.column] {
+            Content::Empty => {
+                self.path.push(ahead.clone());
+                self.visited.insert(ahead.clone());
+                self.location = ahead;
+                Action::Moved
+            }
+            Content::Object => {
+                self.direction = turn_right(&self.direction);
+                Action::Turned
+            }
+        };
+
+        if !self.states.insert((self.location.clone(), self.direction)) {
+            self.looped = true;
+        }
+
+        Some(Step {
+            location: self.location.clone(),
+            direction: self.direction,
+            action,
+        })
+    }
+}
+
+impl fmt::Display for GuardSim<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (row, row_content) in self.map.iter().enumerate() {
+            for (column, content) in row_content.iter().enumerate() {
+                let location = Location { row, column };
+
+                if location == self.location {
+                    match self.direction {
+                        Direction::North => write!(f, "^")?,
+                        Direction::East => write!(f, ">")?,
+                        Direction::South => write!(f, "v")?,
+                        Direction::West => write!(f, "<")?,
+                    }
+                } else if self.visited.contains(&location) {
+                    write!(f, "X")?
+                } else {
+                    match content {
+                        Content::Empty => write!(f, ".")?,
+                        Content::Object => write!(f, "#")?,
+                    }
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Simulation for GuardSim<'_> {
+    fn step(&mut self) -> bool {
+        self.next().is_some()
+    }
+}
+
+fn ahead(map: &[Vec<Content>], location: &Location, direction: &Direction) -> Option<Location> {
+    let (row, column): (i32, i32) = match direction {
+        Direction::North => (location.row as i32 - 1, location.column as i32),
+        Direction::East => (location.row as i32, location.column as i32 + 1),
+        Direction::South => (location.row as i32 + 1, location.column as i32),
+        Direction::West => (location.row as i32, location.column as i32 - 1),
+    };
+
+    if row < 0 || row >= map.len() as i32 || column < 0 || column >= map[0].len() as i32 {
+        None
+    } else {
+        Some(Location {
+            row: row as usize,
+            column: column as usize,
+        })
+    }
+}
+
+fn turn_right(direction: &Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North,
+    }
+}
+
+pub fn solve_part_1(map: &[Vec<Content>], start_location: &Location) -> usize {
+    let mut sim = GuardSim::new(map, start_location);
+    sim.by_ref().for_each(drop);
+
+    sim.visited().len()
+}
+
+// For each cell and direction (in `Direction` order), the cell the guard stops on when walking
+// that way until an obstacle, or `EXIT` if it walks off the map.
+struct Jumps {
+    width: usize,
+    stops: [Vec<u32>; 4],
+}
+
+const EXIT: u32 = u32::MAX;
+
+impl Jumps {
+    fn new(map: &[Vec<Content>]) -> Self {
+        let (height, width) = (map.len(), map[0].len());
+        let cells = (0..height).flat_map(|row| (0..width).map(move |column| (row, column)));
+        let mut stops = [0; 4].map(|_| vec![EXIT; width * height]);
+
+        // Each direction is filled in from the edge it faces, so the cell ahead is always done.
+        let orders: [Vec<(usize, usize)>; 4] = [
+            cells.clone().collect(),
+            cells
+                .clone()
+                .sorted_by_key(|(row, column)| (Reverse(*column), *row))
+                .collect(),
+            cells.clone().rev().collect(),
+            cells
+                .sorted_by_key(|(row, column)| (*column, *row))
+                .collect(),
+        ];
+
+        for (direction, order) in orders.iter().enumerate() {
+            for (row, column) in order {
+                let location = Location {
+                    row: *row,
+                    column: *column,
+                };
+                let Some(ahead) = ahead(map, &location, &DIRECTIONS[direction]) else {
+                    continue;
+                };
+
+                stops[direction][row * width + column] = match map[ahead.row][ahead.column] {
+                    Content::Object => (row * width + column) as u32,
+                    Content::Empty => stops[direction][ahead.row * width + ahead.column],
+                };
+            }
+        }
+
+        Jumps { width, stops }
+    }
+
+    // How far `to` is from `from` walking in `direction`, if it is on the way at all.
+    fn distance(&self, from: usize, to: usize, direction: usize) -> Option<usize> {
+        let (from_row, from_column) = (from / self.width, from % self.width);
+        let (to_row, to_column) = (to / self.width, to % self.width);
+
+        match direction {
+            0 => (from_column == to_column && to_row <= from_row).then(|| from_row - to_row),
+            1 => (from_row == to_row && to_column >= from_column).then(|| to_column - from_column),
+            2 => (from_column == to_column && to_row >= from_row).then(|| to_row - from_row),
+            _ => (from_row == to_row && to_column <= from_column).then(|| from_column - to_column),
+        }
+    }
+
+    // Walks from `cell` with an extra obstacle, a turn at a time. If it loops, the turns of the
+    // loop as `cell * 4 + direction` after turning, from the top left most one so the same loop
+    // comes out the same wherever the walk started.
+    fn cycle(
+        &self,
+        mut cell: usize,
+        mut direction: usize,
+        obstacle: usize,
+        seen: &mut BitSet,
+    ) -> Option<Vec<usize>> {
+        let mut turns = vec![];
+        seen.clear();
+
+        loop {
+            let stop = self.stops[direction][cell];
+            let blocked = self.distance(cell, obstacle, direction).filter(|distance| {
+                *distance > 0
+                    && (stop == EXIT
+                        || *distance <= self.distance(cell, stop as usize, direction).unwrap())
+            });
+
+            cell = match (blocked, direction) {
+                (Some(_), 0) => obstacle + self.width,
+                (Some(_), 1) => obstacle - 1,
+                (Some(_), 2) => obstacle - self.width,
+                (Some(_), _) => obstacle + 1,
+                (None, _) if stop == EXIT => return None,
+                (None, _) => stop as usize,
+            };
+            direction = (direction + 1) % 4;
+
+            let turn = cell * 4 + direction;
+            if !seen.insert(turn) {
+                let start = turns.iter().position(|t| *t == turn).unwrap();
+                let mut cycle = turns.split_off(start);
+                let first = cycle.iter().position_min().unwrap();
+                cycle.rotate_left(first);
+
+                return Some(cycle);
+            }
+            turns.push(turn);
+        }
+    }
+
+    // The cells the guard steps onto going once round a loop of turns.
+    fn cells(&self, turns: &[usize]) -> Vec<Location> {
+        let mut cells = vec![];
+
+        for (from, to) in turns.iter().zip(turns.iter().cycle().skip(1)) {
+            let (mut cell, direction, to) = (from / 4, from % 4, to / 4);
+
+            while cell != to {
+                cell = match direction {
+                    0 => cell - self.width,
+                    1 => cell + 1,
+                    2 => cell + self.width,
+                    _ => cell - 1,
+                };
+                cells.push(Location {
+                    row: cell / self.width,
+                    column: cell % self.width,
+                });
+            }
+        }
+
+        cells
+    }
+}
+
+// A flat set of small integers that remembers which words to zero when cleared.
+struct BitSet {
+    words: Vec<u64>,
+    used: Vec<usize>,
+}
+
+impl BitSet {
+    fn new(size: usize) -> Self {
+        BitSet {
+            words: vec![0; size.div_ceil(64)],
+            used: vec![],
+        }
+    }
+
+    fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / 64, 1 << (value % 64));
+        let added = self.words[word] & bit == 0;
+
+        self.words[word] |= bit;
+        self.used.push(word);
+
+        added
+    }
+
+    fn clear(&mut self) {
+        for word in self.used.drain(..) {
+            self.words[word] = 0;
+        }
+    }
+}
+
+const DIRECTIONS: [Direction; 4] = [
+    Direction::North,
+    Direction::East,
+    Direction::South,
+    Direction::West,
+];
+
+/// A new obstacle that sends the guard round in a loop.
+#[derive(Debug, PartialEq)]
+pub struct Trap {
+    pub obstacle: Location,
+    /// Where the guard steps going once round the loop, starting after its top left most turn.
+    /// A cell the loop crosses itself at comes up twice.
+    pub cycle: Vec<Location>,
+}
+
+impl Trap {
+    pub fn length(&self) -> usize {
+        self.cycle.len()
+    }
+}
+
+impl fmt::Display for Trap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "obstacle at {},{} makes a loop of {} steps",
+            self.obstacle.row,
+            self.obstacle.column,
+            self.length()
+        )
+    }
+}
+
+/// Every place a single new obstacle traps the guard, in the order the guard would first have
+/// walked into them. Each is tried from the step just before the guard's original path reaches
+/// it, since the path up to there is unchanged, and the candidates are shared between `threads`
+/// threads, at most one per available core.
+pub fn traps(map: &[Vec<Content>], start_location: &Location, threads: usize) -> Vec<Trap> {
+    let jumps = Jumps::new(map);
+    let width = jumps.width;
+
+    let mut before = (start_location.clone(), Direction::North);
+    let mut tried = hashset![start_location.clone()];
+    let mut candidates = vec![];
+    for step in GuardSim::new(map, start_location) {
+        if step.action == Action::Moved && tried.insert(step.location.clone()) {
+            candidates.push((before.clone(), step.location.clone()));
+        }
+        before = (step.location, step.direction);
+    }
+
+    let check = |candidates: &[((Location, Direction), Location)]| {
+        let mut seen = BitSet::new(map.len() * width * 4);
+
+        candidates
+            .iter()
+            .filter_map(|((location, direction), obstacle)| {
+                let cell = location.row * width + location.column;
+                let turns = jumps.cycle(
+                    cell,
+                    *direction as usize,
+                    obstacle.row * width + obstacle.column,
+                    &mut seen,
+                )?;
+
+                Some(Trap {
+                    obstacle: obstacle.clone(),
+                    cycle: jumps.cells(&turns),
+                })
+            })
+            .collect::<Vec<Trap>>()
+    };
+
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    let threads = threads.clamp(1, cores);
+    if threads == 1 {
+        return check(&candidates);
+    }
+
+    let chunk_size = candidates.len().div_ceil(threads).max(1);
+    thread::scope(|scope| {
+        candidates
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(|| check(chunk)))
+            .collect_vec()
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// The loop a new obstacle at `obstacle` would trap the guard in, if any.
+pub fn trap(map: &[Vec<Content>], start_location: &Location, obstacle: &Location) -> Option<Trap> {
+    let placeable = map
+        .get(obstacle.row)
+        .and_then(|row| row.get(obstacle.column))
+        .is_some_and(|content| *content == Content::Empty);
+    if !placeable || obstacle == start_location {
+        return None;
+    }
+
+    let jumps = Jumps::new(map);
+    let turns = jumps.cycle(
+        start_location.row * jumps.width + start_location.column,
+        Direction::North as usize,
+        obstacle.row * jumps.width + obstacle.column,
+        &mut BitSet::new(map.len() * jumps.width * 4),
+    )?;
+
+    Some(Trap {
+        obstacle: obstacle.clone(),
+        cycle: jumps.cells(&turns),
+    })
+}
+
+/// The map with the trap's obstacle as `O`, its loop as `+` and the guard's start as `^`.
+pub fn render_trap(map: &[Vec<Content>], start_location: &Location, trap: &Trap) -> String {
+    let cycle = trap.cycle.iter().collect::<HashSet<&Location>>();
+    let mut result = String::new();
+
+    for (row, row_content) in map.iter().enumerate() {
+        for (column, content) in row_content.iter().enumerate() {
+            let location = Location { row, column };
+
+            result.push(match content {
+                Content::Object => '#',
+                _ if location == trap.obstacle => 'O',
+                _ if location == *start_location => '^',
+                _ if cycle.contains(&location) => '+',
+                Content::Empty => '.',
+            });
+        }
+        result.push('\n');
+    }
+
+    result
+}
+
+pub fn solve_part_2(map: &[Vec<Content>], location: &Location) -> usize {
+    traps(map, location, 1).len()
+}
+
+pub fn path_image(map: &[Vec<Content>], start_location: &Location) -> Image {
+    let mut walk = GuardSim::new(map, start_location);
+    while walk.step() {}
+
+    let palette = hashmap! {
+        '#' => [128, 128, 128],
+        'X' => [0, 160, 255],
+        '^' => [255, 0, 0],
+        '>' => [255, 0, 0],
+        'v' => [255, 0, 0],
+        '<' => [255, 0, 0],
+    };
+
+    Image::from_text(&walk.to_string(), &palette, BLACK)
+}
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn solve(&self, part: u8, input: &str, params: &Params) -> Result<String, SolveError> {
+        check_grid(input, |c| ".#^".contains(c))?;
+        if input.matches('^').count() != 1 {
+            return Err(SolveError::InvalidInput);
+        }
+        let (map, location) = parse_input(input);
+
+        match part {
+            1 => Ok(solve_part_1(&map, &location).to_string()),
+            2 => Ok(traps(&map, &location, param(params, "threads", 1)?)
+                .len()
+                .to_string()),
+            _ => Err(SolveError::UnknownPart(part)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::day_6::Content::{Empty, Object};
+    use crate::days::day_6::Direction::{East, North};
+    use crate::days::day_6::{
+        parse_input, path_image, render_trap, trap, traps, Action, Content, GuardSim, Location,
+        Step,
+    };
+    use crate::image::BLACK;
+    use crate::terminal::Simulation;
+    use crate::testing::Rng;
+    use std::collections::HashSet;
+
+    const EXAMPLE_INPUT: &str = "....#.....\n\
+        .........#\n\
+        ..........\n\
+        ..#.......\n\
+        .......#..\n\
+        ..........\n\
+        .#..^.....\n\
+        ........#.\n\
+        #.........\n\
+        ......#...\n";
+    #[test]
+    fn test_parse_input() {
+        let (map, location) = parse_input(".#.\n.^.\n");
+
+        assert_eq!(
+            map,
+            vec![vec![Empty, Object, Empty], vec![Empty, Empty, Empty]]
+        );
+        assert_eq!(location, Location { row: 1, column: 1 });
+    }
+
+    #[test]
+    fn test_guard_walk_display() {
+        let (map, location) = parse_input(".#.\n...\n.^.\n");
+        let mut walk = GuardSim::new(&map, &location);
+
+        assert_eq!(".#.\n...\n.^.\n", walk.to_string());
+        assert!(walk.step());
+        assert!(walk.step());
+        assert_eq!(".#.\n.>.\n.X.\n", walk.to_string());
+    }
+
+    #[test]
+    fn test_guard_walk_matches_part_1() {
+        let (map, location) = parse_input(EXAMPLE_INPUT);
+        let mut walk = GuardSim::new(&map, &location);
+
+        while walk.step() {}
+
+        assert_eq!(41, walk.visited.len());
+    }
+
+    #[test]
+    fn test_guard_sim_steps() {
+        let (map, location) = parse_input(".#.\n...\n.^.\n");
+        let mut sim = GuardSim::new(&map, &location);
+        let at = |row, column| Location { row, column };
+
+        assert_eq!(
+            vec![
+                Step {
+                    location: at(1, 1),
+                    direction: North,
+                    action: Action::Moved
+                },
+                Step {
+                    location: at(1, 1),
+                    direction: East,
+                    action: Action::Turned
+                },
+                Step {
+                    location: at(1, 2),
+                    direction: East,
+                    action: Action::Moved
+                },
+            ],
+            sim.by_ref().collect::<Vec<Step>>()
+        );
+        assert_eq!(&[at(2, 1), at(1, 1), at(1, 2)], sim.path());
+        assert!(!sim.looped());
+    }
+
+    #[test]
+    fn test_guard_sim_in_pieces() {
+        let (map, location) = parse_input(EXAMPLE_INPUT);
+        let mut sim = GuardSim::new(&map, &location);
+
+        assert_eq!(3, sim.by_ref().take(3).count());
+        sim.by_ref().for_each(drop);
+
+        assert_eq!(41, sim.visited().len());
+        assert_eq!(Location { row: 9, column: 7 }, *sim.location());
+    }
+
+    #[test]
+    fn test_guard_sim_loop() {
+        let (mut map, location) = parse_input(EXAMPLE_INPUT);
+        map[6][3] = Object;
+        let mut sim = GuardSim::new(&map, &location);
+
+        sim.by_ref().for_each(drop);
+
+        assert!(sim.looped());
+        assert_eq!(None, sim.next());
+    }
+
+    #[test]
+    fn test_path_image() {
+        let (map, location) = parse_input(".#.\n...\n.^.\n");
+        let image = path_image(&map, &location);
+
+        assert_eq!([128, 128, 128], image.get(1, 0));
+        assert_eq!([0, 160, 255], image.get(1, 2));
+        assert_eq!([255, 0, 0], image.get(2, 1));
+        assert_eq!(BLACK, image.get(0, 0));
+    }
+
+    #[test]
+    fn test_solve_part_1() {
+        let (map, location) = parse_input(EXAMPLE_INPUT);
+        assert_eq!(crate::days::day_6::solve_part_1(&map, &location), 41);
+    }
+    #[test]
+    fn test_solve_part_2() {
+        let (map, location) = parse_input(EXAMPLE_INPUT);
+        assert_eq!(crate::days::day_6::solve_part_2(&map, &location), 6);
+    }
+
+    // The original approach: walk the whole route again with each candidate obstacle added.
+    fn traps_by_rewalking(map: &[Vec<Content>], location: &Location) -> HashSet<Location> {
+        let mut sim = GuardSim::new(map, location);
+        sim.by_ref().for_each(drop);
+
+        sim.visited()
+            .iter()
+            .filter(|obstacle| *obstacle != location)
+            .filter(|obstacle| {
+                let mut map_copy = map.to_vec();
+                map_copy[obstacle.row][obstacle.column] = Object;
+
+                let mut sim = GuardSim::new(&map_copy, location);
+                sim.by_ref().for_each(drop);
+                sim.looped()
+            })
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_traps() {
+        let (map, location) = parse_input(EXAMPLE_INPUT);
+        let at = |row, column| Location { row, column };
+        let found = traps(&map, &location, 1);
+
+        assert_eq!(
+            vec![at(6, 3), at(7, 6), at(8, 3), at(8, 1), at(7, 7), at(9, 7)],
+            found
+                .iter()
+                .map(|t| t.obstacle.clone())
+                .collect::<Vec<Location>>()
+        );
+        assert_eq!(found, traps(&map, &location, 4));
+        assert_eq!(found, traps(&map, &location, 100_000));
+        assert_eq!(
+            vec![at(1, 5), at(1, 6), at(1, 7), at(1, 8), at(2, 8), at(3, 8)],
+            found[0].cycle[..6]
+        );
+        assert_eq!(
+            "obstacle at 6,3 makes a loop of 18 steps",
+            found[0].to_string()
+        );
+        assert_eq!(Some(&found[3]), trap(&map, &location, &at(8, 1)).as_ref());
+        assert_eq!(None, trap(&map, &location, &at(0, 0)));
+        assert_eq!(None, trap(&map, &location, &location));
+    }
+
+    #[test]
+    fn test_render_trap() {
+        let (map, location) = parse_input(EXAMPLE_INPUT);
+        let trap = trap(&map, &location, &Location { row: 6, column: 3 }).unwrap();
+
+        assert_eq!(
+            "....#.....\n\
+            ....+++++#\n\
+            ....+...+.\n\
+            ..#.+...+.\n\
+            ....+..#+.\n\
+            ....+...+.\n\
+            .#.O^++++.\n\
+            ........#.\n\
+            #.........\n\
+            ......#...\n",
+            render_trap(&map, &location, &trap)
+        );
+    }
+
+    #[test]
+    fn test_traps_match_rewalking() {
+        let mut rng = Rng::new(0x853c49e6748fea9b);
+
+        for _ in 0..200 {
+            let (height, width) = (rng.below(12) as usize + 1, rng.below(12) as usize + 1);
+            let mut map = (0..height)
+                .map(|_| {
+                    (0..width)
+                        .map(|_| match rng.below(6) {
+                            0 => Object,
+                            _ => Empty,
+                        })
+                        .collect::<Vec<Content>>()
+                })
+                .collect::<Vec<Vec<Content>>>();
+            let location = Location {
+                row: rng.below(height as u64) as usize,
+                column: rng.below(width as u64) as usize,
+            };
+            map[location.row][location.column] = Empty;
+
+            assert_eq!(
+                traps_by_rewalking(&map, &location),
+                traps(&map, &location, 3)
+                    .into_iter()
+                    .map(|t| t.obstacle)
+                    .collect(),
+                "{:?} from {:?}",
+                map,
+                location
+            );
+        }
+    }
+}
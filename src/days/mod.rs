@@ -0,0 +1,115 @@
+//! One module per puzzle day, each exposing its parser, both part solvers and a `Solver`.
+
+use crate::solver::{Params, SolveError, Solver};
+use std::panic::{self, AssertUnwindSafe};
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_11;
+pub mod day_12;
+pub mod day_13;
+pub mod day_14;
+pub mod day_15;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+pub static SOLVERS: [&dyn Solver; 15] = [
+    &day_1::Day1,
+    &day_2::Day2,
+    &day_3::Day3,
+    &day_4::Day4,
+    &day_5::Day5,
+    &day_6::Day6,
+    &day_7::Day7,
+    &day_8::Day8,
+    &day_9::Day9,
+    &day_10::Day10,
+    &day_11::Day11,
+    &day_12::Day12,
+    &day_13::Day13,
+    &day_14::Day14,
+    &day_15::Day15,
+];
+
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().copied().find(|s| s.day() == day)
+}
+
+/// Runs a day's solver. Solvers turn away input they can't parse themselves; a panic that still
+/// gets through is reported as `SolveError::InvalidInput` rather than taking the caller down.
+pub fn solve(day: u8, part: u8, input: &str, params: &Params) -> Result<String, SolveError> {
+    let solver = solver(day).ok_or(SolveError::UnknownDay(day))?;
+
+    panic::catch_unwind(AssertUnwindSafe(|| solver.solve(part, input, params)))
+        .unwrap_or(Err(SolveError::InvalidInput))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::solver;
+    use crate::solver::{Params, SolveError};
+    use maplit::hashmap;
+    use rstest::rstest;
+
+    // Called on the solvers directly, so a panic fails the test rather than being caught.
+    #[rstest]
+    #[case(1, "1 2\n3 x\n")]
+    #[case(2, "1 2 three\n")]
+    #[case(4, "XMAS\nXM\n")]
+    #[case(5, "1|2\n\n1,2,x\n")]
+    #[case(5, "1|2\n")]
+    #[case(6, "..#\n...\n")]
+    #[case(6, "..^\n.^.\n")]
+    #[case(7, "190 10 19\n")]
+    #[case(8, "a.\n...\n")]
+    #[case(9, "12a45\n")]
+    #[case(10, "0123\n98x6\n")]
+    #[case(11, "125 -17\n")]
+    #[case(12, "")]
+    #[case(13, "Button A: X+0, Y+1\nButton B: X+1, Y+1\nPrize: X=5, Y=5\n")]
+    #[case(14, "p=0,4 v=3\n")]
+    #[case(15, "#####\n#@.O.\n#####\n\n<>\n")]
+    fn test_solvers_reject_malformed_input(#[case] day: u8, #[case] input: &str) {
+        let params = Params::new();
+
+        for part in 1..=2 {
+            assert_eq!(
+                Err(SolveError::InvalidInput),
+                solver(day).unwrap().solve(part, input, &params)
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(11, 1, "125 17", "blinks", "36")]
+    #[case(11, 2, "125 17", "blinks", "101")]
+    #[case(
+        13,
+        2,
+        "Button A: X+1, Y+1\nButton B: X+1, Y+1\nPrize: X=5, Y=5",
+        "offset",
+        "10000000000000000000"
+    )]
+    #[case(14, 1, "p=0,4 v=3,-3", "width", "0")]
+    #[case(14, 1, "p=0,4 v=3,-3", "seconds", "1000000")]
+    fn test_solvers_bound_parameters(
+        #[case] day: u8,
+        #[case] part: u8,
+        #[case] input: &str,
+        #[case] name: &str,
+        #[case] value: &str,
+    ) {
+        let params = hashmap! {name.to_string() => value.to_string()};
+
+        assert_eq!(
+            Err(SolveError::BadParameter(name.to_string())),
+            solver(day).unwrap().solve(part, input, &params)
+        );
+    }
+}
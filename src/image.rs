@@ -0,0 +1,327 @@
+use crate::terminal::Simulation;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const MAX_STORED_BLOCK: usize = 65535;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// One pixel per cell of a row-major grid, coloured by `colour`.
+    pub fn from_grid<T>(grid: &[Vec<T>], colour: impl Fn(&T) -> Rgb) -> Self {
+        let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut image = Image::new(width, grid.len(), BLACK);
+
+        for (y, row) in grid.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                image.set(x, y, colour(cell));
+            }
+        }
+
+        image
+    }
+
+    /// Plots each `(x, y)` point in `foreground`, ignoring any that fall outside the image.
+    pub fn from_points(
+        width: usize,
+        height: usize,
+        points: impl IntoIterator<Item = (usize, usize)>,
+        background: Rgb,
+        foreground: Rgb,
+    ) -> Self {
+        let mut image = Image::new(width, height, background);
+
+        for (x, y) in points {
+            if x < width && y < height {
+                image.set(x, y, foreground);
+            }
+        }
+
+        image
+    }
+
+    /// Renders text such as a `Display` frame, one pixel per character.
+    pub fn from_text(text: &str, palette: &HashMap<char, Rgb>, default: Rgb) -> Self {
+        let grid: Vec<Vec<char>> = text.lines().map(|l| l.chars().collect()).collect();
+
+        Image::from_grid(&grid, |c| *palette.get(c).unwrap_or(&default))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, colour: Rgb) {
+        self.pixels[y * self.width + x] = colour;
+    }
+
+    /// Blows every pixel up into a `factor` x `factor` square so small grids are visible.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let mut image = Image::new(self.width * factor, self.height * factor, BLACK);
+
+        for y in 0..image.height {
+            for x in 0..image.width {
+                image.set(x, y, self.get(x / factor, y / factor));
+            }
+        }
+
+        image
+    }
+
+    pub fn write_ppm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+
+        for pixel in &self.pixels {
+            out.write_all(pixel)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a truecolour PNG; the image data is stored rather than compressed.
+    pub fn write_png<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // 8 bits per channel, truecolour, default compression, filter and no interlacing
+        header.extend([8, 2, 0, 0, 0]);
+
+        let mut scanlines = Vec::with_capacity(self.height * (self.width * 3 + 1));
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            scanlines.push(0);
+            scanlines.extend(row.iter().flatten());
+        }
+
+        out.write_all(&PNG_SIGNATURE)?;
+        write_png_chunk(out, b"IHDR", &header)?;
+        write_png_chunk(out, b"IDAT", &zlib_stored(&scanlines))?;
+        write_png_chunk(out, b"IEND", &[])
+    }
+
+    /// Saves as PNG when the path ends in `.png`, otherwise as PPM.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+
+        if path.extension().is_some_and(|e| e == "png") {
+            self.write_png(&mut out)?;
+        } else {
+            self.write_ppm(&mut out)?;
+        }
+
+        out.flush()
+    }
+}
+
+/// A colour for the `index`th category, spread around the hue circle so neighbours differ.
+pub fn distinct_colour(index: usize) -> Rgb {
+    let hue = (index as f64 * 137.508) % 360.0;
+    let sector = hue / 60.0;
+    let x = 1.0 - (sector % 2.0 - 1.0).abs();
+    let (r, g, b) = match sector as usize {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let channel = |v: f64| (64.0 + v * 191.0) as u8;
+
+    [channel(r), channel(g), channel(b)]
+}
+
+/// Renders up to `count` frames of a simulation into `directory` as `frame_00000.png` onwards.
+///
+/// Returns the number of frames written, which is less than `count` if the simulation ends.
+pub fn save_frames<S: Simulation>(
+    simulation: &mut S,
+    count: usize,
+    palette: &HashMap<char, Rgb>,
+    scale: usize,
+    directory: &Path,
+) -> io::Result<usize> {
+    fs::create_dir_all(directory)?;
+
+    for frame in 0..count {
+        Image::from_text(&simulation.to_string(), palette, BLACK)
+            .scaled(scale)
+            .save(&directory.join(format!("frame_{:05}.png", frame)))?;
+
+        if frame + 1 < count && !simulation.step() {
+            return Ok(frame + 1);
+        }
+    }
+
+    Ok(count)
+}
+
+fn write_png_chunk<W: Write>(out: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+
+    let mut crc_input = kind.to_vec();
+    crc_input.extend(data);
+    out.write_all(&crc32(&crc_input).to_be_bytes())
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut result = vec![0x78, 0x01];
+    let blocks = data.chunks(MAX_STORED_BLOCK).collect::<Vec<_>>();
+
+    if blocks.is_empty() {
+        result.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let length = block.len() as u16;
+
+        result.push(if i == blocks.len() - 1 { 1 } else { 0 });
+        result.extend(length.to_le_bytes());
+        result.extend((!length).to_le_bytes());
+        result.extend(*block);
+    }
+
+    result.extend(adler32(data).to_be_bytes());
+
+    result
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                0xedb88320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::image::{adler32, crc32, distinct_colour, Image, BLACK, WHITE};
+    use maplit::hashmap;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(0xcbf43926, crc32(b"123456789"));
+        assert_eq!(0x11e60398, adler32(b"Wikipedia"));
+    }
+
+    #[test]
+    fn test_from_text() {
+        let image = Image::from_text("#.\n.#\n", &hashmap! {'#' => WHITE}, BLACK);
+
+        assert_eq!(2, image.width());
+        assert_eq!(2, image.height());
+        assert_eq!(WHITE, image.get(0, 0));
+        assert_eq!(BLACK, image.get(1, 0));
+        assert_eq!(WHITE, image.get(1, 1));
+    }
+
+    #[test]
+    fn test_from_points_ignores_outside() {
+        let image = Image::from_points(3, 2, [(2, 1), (3, 0)], BLACK, WHITE);
+
+        assert_eq!(
+            Image::from_grid(&[vec![0, 0, 0], vec![0, 0, 1]], |c| if *c == 1 {
+                WHITE
+            } else {
+                BLACK
+            }),
+            image
+        );
+    }
+
+    #[test]
+    fn test_scaled() {
+        let image = Image::from_points(2, 1, [(1, 0)], BLACK, WHITE).scaled(2);
+
+        assert_eq!(4, image.width());
+        assert_eq!(2, image.height());
+        assert_eq!(BLACK, image.get(1, 1));
+        assert_eq!(WHITE, image.get(2, 1));
+    }
+
+    #[test]
+    fn test_write_ppm() {
+        let mut out = vec![];
+        Image::from_points(2, 1, [(1, 0)], BLACK, WHITE)
+            .write_ppm(&mut out)
+            .unwrap();
+
+        assert_eq!(b"P6\n2 1\n255\n\0\0\0\xff\xff\xff".to_vec(), out);
+    }
+
+    #[test]
+    fn test_write_png() {
+        let mut out = vec![];
+        Image::from_points(1, 1, [(0, 0)], BLACK, WHITE)
+            .write_png(&mut out)
+            .unwrap();
+
+        assert_eq!(
+            b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x01\0\0\0\x01",
+            &out[..24]
+        );
+        // scanline is the filter byte followed by one white pixel, stored in a single final block
+        assert_eq!(
+            b"IDAT\x78\x01\x01\x04\0\xfb\xff\0\xff\xff\xff",
+            &out[37..52]
+        );
+        assert_eq!(b"\0\0\0\0IEND\xae\x42\x60\x82", &out[out.len() - 12..]);
+    }
+
+    #[test]
+    fn test_distinct_colour() {
+        assert_ne!(distinct_colour(0), distinct_colour(1));
+        assert_ne!(distinct_colour(1), distinct_colour(2));
+    }
+}
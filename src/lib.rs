@@ -0,0 +1,12 @@
+//! Advent of Code 2024 solutions as a library: the puzzle days live under [`days`], and the
+//! binaries, server and REPL are thin front ends over them.
+
+pub mod cli;
+pub mod days;
+pub mod image;
+pub mod repl;
+pub mod server;
+pub mod solver;
+pub mod terminal;
+#[cfg(test)]
+mod testing;
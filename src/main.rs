@@ -1,3 +1,39 @@
+use advent_2024::cli;
+use advent_2024::repl::Repl;
+use advent_2024::server::{Server, DEFAULT_ADDRESS, DEFAULT_MAX_BODY, DEFAULT_MAX_CONNECTIONS};
+use std::io;
+
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = std::env::args().collect();
+
+    match args.get(1).map(|a| a.as_str()) {
+        Some("serve") => {
+            let address = cli::option(&args, "--address").unwrap_or(DEFAULT_ADDRESS);
+            let max_body = cli::option(&args, "--max-body")
+                .map(|m| m.parse().expect("--max-body expects a number of bytes"))
+                .unwrap_or(DEFAULT_MAX_BODY);
+            let max_connections = cli::option(&args, "--max-connections")
+                .map(|m| {
+                    m.parse()
+                        .expect("--max-connections expects a number of connections")
+                })
+                .unwrap_or(DEFAULT_MAX_CONNECTIONS);
+            let server = Server::bind(address, max_body)
+                .unwrap()
+                .with_max_connections(max_connections);
+
+            println!("serving solvers on http://{}", server.local_addr().unwrap());
+            server.run();
+        }
+        Some("repl") => Repl::new()
+            .run(io::stdin().lock(), &mut io::stdout())
+            .unwrap(),
+        _ => {
+            println!(
+                "usage: advent_2024 serve [--address 127.0.0.1:8024] [--max-body <bytes>] \
+                [--max-connections <n>]"
+            );
+            println!("       advent_2024 repl");
+        }
+    }
 }
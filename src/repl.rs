@@ -0,0 +1,333 @@
+use crate::days::{self, day_12, day_14, day_15, day_5, day_6, day_8};
+use crate::solver::{param, Params};
+use crate::terminal::Simulation;
+use itertools::Itertools;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Instant;
+
+const HELP: &str = "\
+load <day> [path]    load a day's input, by default src/bin/day_<day>_input.txt
+run [part]           run one or both parts with the current parameters
+set <name> <value>   set a parameter: removals, min_step, max_step, plateaus and direction
+                     (day 2), max_digits (day 3), seconds, width and height (day 14),
+                     threads (day 6), blinks (day 11), offset (day 13), moves (days 6 and 15)
+unset <name>         go back to the default for a parameter
+params               list the parameters that are set
+show <structure>     rules (day 5), antennas (day 8) or regions (day 12)
+render               draw the grid for day 6, 14 or 15
+quit                 leave";
+
+pub enum Reply {
+    Output(String),
+    Quit,
+}
+
+/// An interactive session holding the loaded input and parameters between commands.
+#[derive(Default)]
+pub struct Repl {
+    day: Option<u8>,
+    input: String,
+    params: Params,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Repl::default()
+    }
+
+    pub fn load(&mut self, day: u8, input: &str) {
+        self.day = Some(day);
+        self.input = input.to_string();
+    }
+
+    /// Reads commands until `quit` or the end of the input, writing replies and errors to `out`.
+    pub fn run<R: BufRead, W: Write>(&mut self, commands: R, out: &mut W) -> io::Result<()> {
+        self.prompt(out)?;
+
+        for line in commands.lines() {
+            match self.execute(&line?) {
+                Ok(Reply::Quit) => break,
+                Ok(Reply::Output(text)) if text.is_empty() => {}
+                Ok(Reply::Output(text)) => writeln!(out, "{}", text)?,
+                Err(message) => writeln!(out, "error: {}", message)?,
+            }
+
+            self.prompt(out)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn execute(&mut self, line: &str) -> Result<Reply, String> {
+        let words = line.split_whitespace().collect_vec();
+
+        let output = match words[..] {
+            [] => String::new(),
+            ["help"] => HELP.to_string(),
+            ["quit"] | ["exit"] => return Ok(Reply::Quit),
+            ["load", day] => self.load_file(day, None)?,
+            ["load", day, path] => self.load_file(day, Some(path))?,
+            ["run"] => [self.run_part(1)?, self.run_part(2)?].join("\n"),
+            ["run", part] => self.run_part(parse_number(part)?)?,
+            ["set", name, value] => {
+                self.params.insert(name.to_string(), value.to_string());
+                format!("{} = {}", name, value)
+            }
+            ["unset", name] => {
+                self.params.remove(name);
+                String::new()
+            }
+            ["params"] => self
+                .params
+                .iter()
+                .sorted()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .join("\n"),
+            ["show", structure] => self.show(structure)?,
+            ["render"] => self.render()?,
+            _ => return Err(format!("unknown command '{}', try help", line.trim())),
+        };
+
+        Ok(Reply::Output(output))
+    }
+
+    fn prompt<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        match self.day {
+            Some(day) => write!(out, "day {}> ", day)?,
+            None => write!(out, "> ")?,
+        }
+
+        out.flush()
+    }
+
+    fn loaded_day(&self) -> Result<u8, String> {
+        self.day.ok_or("load a day first".to_string())
+    }
+
+    fn load_file(&mut self, day: &str, path: Option<&str>) -> Result<String, String> {
+        let day = parse_number(day)?;
+        if days::solver(day).is_none() {
+            return Err(format!("no solver for day {}", day));
+        }
+
+        let path = path
+            .map(|p| p.to_string())
+            .unwrap_or(format!("src/bin/day_{}_input.txt", day));
+        let input = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+        self.load(day, &input);
+
+        Ok(format!(
+            "loaded {} lines from {}",
+            input.lines().count(),
+            path
+        ))
+    }
+
+    fn run_part(&self, part: u8) -> Result<String, String> {
+        let day = self.loaded_day()?;
+        let start = Instant::now();
+        let answer =
+            days::solve(day, part, &self.input, &self.params).map_err(|e| e.to_string())?;
+
+        Ok(format!("part {}: {} ({:?})", part, answer, start.elapsed()))
+    }
+
+    fn show(&self, structure: &str) -> Result<String, String> {
+        let day = self.loaded_day()?;
+        let input = &self.input;
+
+        match (day, structure) {
+            (5, "rules") => guarded(|| {
+                let (rules, _) = day_5::parse_input(input);
+
+                rules
+                    .iter()
+                    .sorted_by_key(|(before, _)| **before)
+                    .map(|(before, afters)| {
+                        format!("{} -> {}", before, afters.iter().sorted().join(", "))
+                    })
+                    .join("\n")
+            }),
+            (8, "antennas") => guarded(|| {
+                let map = day_8::parse_input(input);
+
+                map.antennas
+                    .iter()
+                    .sorted_by_key(|(frequency, _)| **frequency)
+                    .map(|(frequency, antennas)| {
+                        let locations = antennas
+                            .iter()
+                            .map(|a| format!("({}, {})", a.x, a.y))
+                            .join(" ");
+
+                        format!("{}: {}", frequency, locations)
+                    })
+                    .join("\n")
+            }),
+            (12, "regions") => guarded(|| {
+                day_12::regions(&day_12::parse_map(input))
+                    .iter()
+                    .map(|r| {
+                        format!(
+                            "{} area {} perimeter {} sides {}",
+                            r.plant, r.area, r.perimeter, r.sides
+                        )
+                    })
+                    .join("\n")
+            }),
+            _ => Err(format!("day {} has no {} to show", day, structure)),
+        }
+    }
+
+    fn render(&self) -> Result<String, String> {
+        let day = self.loaded_day()?;
+        let input = &self.input;
+        let moves = param(&self.params, "moves", usize::MAX).map_err(|e| e.to_string())?;
+
+        match day {
+            6 => guarded(|| {
+                let (map, location) = day_6::parse_input(input);
+                let mut walk = day_6::GuardSim::new(&map, &location);
+                advance(&mut walk, moves);
+
+                walk.to_string()
+            }),
+            14 => {
+                let bounds = day_14::Vector {
+                    x: param(&self.params, "width", 101).map_err(|e| e.to_string())?,
+                    y: param(&self.params, "height", 103).map_err(|e| e.to_string())?,
+                };
+                let seconds = param(&self.params, "seconds", 0).map_err(|e| e.to_string())?;
+
+                guarded(|| {
+                    let robots = day_14::parse_input(input);
+
+                    day_14::Lobby::new(bounds, &robots, seconds).to_string()
+                })
+            }
+            15 => guarded(|| {
+                let (room, directions) = day_15::parse_input(input);
+                let mut warehouse = day_15::Warehouse::new(room, &directions);
+                advance(&mut warehouse, moves);
+
+                warehouse.to_string()
+            }),
+            _ => Err(format!("day {} has no grid to render", day)),
+        }
+    }
+}
+
+fn parse_number(s: &str) -> Result<u8, String> {
+    s.parse().map_err(|_| format!("'{}' is not a number", s))
+}
+
+fn advance<S: Simulation>(simulation: &mut S, moves: usize) {
+    for _ in 0..moves {
+        if !simulation.step() {
+            break;
+        }
+    }
+}
+
+// Parsers panic on malformed input; keep the session alive and report it instead.
+fn guarded<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|_| "could not parse the input".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::{Repl, Reply};
+
+    fn output(repl: &mut Repl, line: &str) -> String {
+        match repl.execute(line) {
+            Ok(Reply::Output(text)) => text,
+            Ok(Reply::Quit) => panic!("unexpected quit"),
+            Err(message) => panic!("{}", message),
+        }
+    }
+
+    #[test]
+    fn test_run_with_parameter() {
+        let mut repl = Repl::new();
+        repl.load(11, "125 17\n");
+
+        assert_eq!("blinks = 6", output(&mut repl, "set blinks 6"));
+        assert!(output(&mut repl, "run 1").starts_with("part 1: 22 ("));
+        assert_eq!("blinks = 6", output(&mut repl, "params"));
+
+        output(&mut repl, "unset blinks");
+        assert!(output(&mut repl, "run 1").starts_with("part 1: 55312 ("));
+    }
+
+    #[test]
+    fn test_show_rules() {
+        let mut repl = Repl::new();
+        repl.load(5, "1|2\n3|4\n3|2\n\n1,2,3\n");
+
+        assert_eq!("1 -> 2\n3 -> 2, 4", output(&mut repl, "show rules"));
+    }
+
+    #[test]
+    fn test_show_antennas() {
+        let mut repl = Repl::new();
+        repl.load(8, "a..\n..a\n.0.\n");
+
+        assert_eq!(
+            "0: (1, 2)\na: (0, 0) (2, 1)",
+            output(&mut repl, "show antennas")
+        );
+    }
+
+    #[test]
+    fn test_show_regions() {
+        let mut repl = Repl::new();
+        repl.load(12, "AAB\n");
+
+        assert_eq!(
+            "A area 2 perimeter 6 sides 4\nB area 1 perimeter 4 sides 4",
+            output(&mut repl, "show regions")
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let mut repl = Repl::new();
+        repl.load(14, "p=2,1 v=1,1\n");
+        output(&mut repl, "set width 4");
+        output(&mut repl, "set height 3");
+        output(&mut repl, "set seconds 1");
+
+        assert_eq!("....\n....\n...1\n", output(&mut repl, "render"));
+
+        repl.load(6, "#..\n...\n^..\n");
+        output(&mut repl, "set moves 2");
+
+        assert_eq!("#..\n>..\nX..\n", output(&mut repl, "render"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut repl = Repl::new();
+
+        assert!(repl.execute("run").is_err());
+        assert!(repl.execute("frobnicate").is_err());
+        assert!(repl.execute("load 99").is_err());
+
+        repl.load(1, "not numbers");
+        assert!(repl.execute("run 1").is_err());
+        assert!(repl.execute("show rules").is_err());
+    }
+
+    #[test]
+    fn test_session() {
+        let mut repl = Repl::new();
+        let mut out = vec![];
+
+        repl.run("set blinks 1\nquit\nhelp\n".as_bytes(), &mut out)
+            .unwrap();
+
+        assert_eq!("> blinks = 1\n> ", String::from_utf8(out).unwrap());
+    }
+}
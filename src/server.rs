@@ -0,0 +1,473 @@
+use crate::days;
+use crate::solver::{Params, SolveError};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8024";
+pub const DEFAULT_MAX_BODY: usize = 1024 * 1024;
+pub const DEFAULT_MAX_CONNECTIONS: usize = 8;
+
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(100);
+// Some solvers recurse once per cell, e.g. day 12's region fill, and inputs can be as large as
+// `max_body` allows.
+const CONNECTION_STACK_SIZE: usize = 256 * 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub params: Params,
+    pub body: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    fn error(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            422 => "Unprocessable Entity",
+            503 => "Service Unavailable",
+            _ => "Internal Server Error",
+        }
+    }
+
+    fn write_to<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(
+            out,
+            "HTTP/1.1 {} {}\r\n\
+            Content-Type: application/json\r\n\
+            Content-Length: {}\r\n\
+            Connection: close\r\n\
+            \r\n\
+            {}",
+            self.status,
+            self.reason(),
+            self.body.len(),
+            self.body
+        )?;
+
+        out.flush()
+    }
+}
+
+/// Serves `POST /day/{n}/part/{p}` with the puzzle input as the body and parameters in the
+/// query string, e.g. `/day/11/part/1?blinks=6`.
+pub struct Server {
+    listener: TcpListener,
+    max_body: usize,
+    max_connections: usize,
+    active: Arc<AtomicUsize>,
+}
+
+impl Server {
+    /// Binds to a loopback address; anything else is refused so the solvers stay local.
+    pub fn bind(address: &str, max_body: usize) -> io::Result<Self> {
+        let addresses = address.to_socket_addrs()?.collect::<Vec<SocketAddr>>();
+
+        if addresses.is_empty() || addresses.iter().any(|a| !a.ip().is_loopback()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("refusing to serve on non-local address {}", address),
+            ));
+        }
+
+        Ok(Server {
+            listener: TcpListener::bind(&addresses[..])?,
+            max_body,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            active: Arc::new(AtomicUsize::new(0)),
+        })
+    }
+
+    pub fn with_max_connections(self, max_connections: usize) -> Self {
+        Server {
+            max_connections,
+            ..self
+        }
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Handles connections forever, one thread per connection up to `max_connections` at once;
+    /// any more are turned away as busy. A connection that can't be accepted, say because the
+    /// process is out of file descriptors, is logged and skipped.
+    pub fn run(&self) {
+        for stream in self.listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("failed to accept a connection: {}", e);
+                    thread::sleep(ACCEPT_RETRY_DELAY);
+                    continue;
+                }
+            };
+
+            if self.active.load(Ordering::SeqCst) >= self.max_connections {
+                let busy = Response::error(503, "too many requests at once, try again later");
+                let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+                let _ = busy.write_to(&mut &stream);
+                continue;
+            }
+
+            let slot = Slot::take(&self.active);
+            let max_body = self.max_body;
+            let spawned = thread::Builder::new()
+                .stack_size(CONNECTION_STACK_SIZE)
+                .spawn(move || {
+                    let _slot = slot;
+                    handle_connection(stream, max_body)
+                });
+            if let Err(e) = spawned {
+                eprintln!("failed to start a connection thread: {}", e);
+            }
+        }
+    }
+}
+
+// Counts a connection as active until it is dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    fn take(active: &Arc<AtomicUsize>) -> Self {
+        active.fetch_add(1, Ordering::SeqCst);
+        Slot(Arc::clone(active))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn handle_connection(stream: TcpStream, max_body: usize) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let response = match read_request(&mut reader, max_body) {
+        Ok(request) => route(&request),
+        Err(response) => response,
+    };
+
+    response.write_to(&mut &stream)
+}
+
+pub fn read_request<R: BufRead>(reader: &mut R, max_body: usize) -> Result<Request, Response> {
+    let bad_request = |message: &str| Response::error(400, message);
+
+    let mut head = reader.take(MAX_HEADER_BYTES);
+    let mut request_line = String::new();
+    head.read_line(&mut request_line)
+        .map_err(|_| bad_request("unreadable request"))?;
+
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(bad_request("malformed request line"));
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if head
+            .read_line(&mut line)
+            .map_err(|_| bad_request("unreadable headers"))?
+            == 0
+        {
+            return Err(bad_request("headers too long or incomplete"));
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| bad_request("invalid content length"))?;
+            }
+        }
+    }
+
+    if content_length > max_body {
+        return Err(Response::error(
+            413,
+            &format!("input is limited to {} bytes", max_body),
+        ));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad_request("body shorter than content length"))?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        params: parse_query(query),
+        body: String::from_utf8(body).map_err(|_| bad_request("input is not UTF-8"))?,
+    })
+}
+
+fn parse_query(query: &str) -> Params {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (name, value) = p.split_once('=').unwrap_or((p, ""));
+
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+// Undoes query string encoding, where `+` is a space and `%XX` a byte. A `%` not followed by two
+// hex digits is kept as it is.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+            .map(|hex| u8::from_str_radix(std::str::from_utf8(hex).unwrap(), 16).unwrap());
+
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+pub fn route(request: &Request) -> Response {
+    let segments = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<&str>>();
+
+    let ["day", day, "part", part] = segments[..] else {
+        return Response::error(404, "expected /day/{n}/part/{p}");
+    };
+    let (Ok(day), Ok(part)) = (day.parse::<u8>(), part.parse::<u8>()) else {
+        return Response::error(404, "day and part must be numbers");
+    };
+    if request.method != "POST" {
+        return Response::error(405, "use POST with the puzzle input as the body");
+    }
+
+    let start = Instant::now();
+    let result = days::solve(day, part, &request.body, &request.params);
+    let elapsed = start.elapsed();
+
+    match result {
+        Ok(answer) => Response {
+            status: 200,
+            body: format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_micros\":{}}}",
+                day,
+                part,
+                json_string(&answer),
+                elapsed.as_micros()
+            ),
+        },
+        Err(e @ (SolveError::UnknownDay(_) | SolveError::UnknownPart(_))) => {
+            Response::error(404, &e.to_string())
+        }
+        Err(e @ SolveError::BadParameter(_)) => Response::error(400, &e.to_string()),
+        Err(e @ SolveError::InvalidInput) => Response::error(422, &e.to_string()),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::{json_string, percent_decode, read_request, route, Request, Server};
+    use maplit::hashmap;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    fn post(path: &str, body: &str) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            params: hashmap! {},
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /day/11/part/1?blinks=6 HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Content-Length: 6\r\n\
+            \r\n\
+            125 17";
+
+        assert_eq!(
+            Request {
+                method: "POST".to_string(),
+                path: "/day/11/part/1".to_string(),
+                params: hashmap! {"blinks".to_string() => "6".to_string()},
+                body: "125 17".to_string(),
+            },
+            read_request(&mut raw.as_bytes(), 100).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_request_rejects_large_body() {
+        let raw = "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: 101\r\n\r\n";
+
+        assert_eq!(
+            413,
+            read_request(&mut raw.as_bytes(), 100).unwrap_err().status
+        );
+    }
+
+    #[test]
+    fn test_route_solves() {
+        let response = route(&post("/day/1/part/2", "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n"));
+
+        assert_eq!(200, response.status);
+        assert!(response
+            .body
+            .starts_with("{\"day\":1,\"part\":2,\"answer\":\"31\",\"elapsed_micros\":"));
+    }
+
+    #[test]
+    fn test_route_passes_params() {
+        let mut request = post("/day/11/part/2", "125 17\n");
+        request.params = hashmap! {"blinks".to_string() => "6".to_string()};
+
+        assert!(route(&request).body.contains("\"answer\":\"22\""));
+    }
+
+    #[test]
+    fn test_route_errors() {
+        assert_eq!(404, route(&post("/day/26/part/1", "")).status);
+        assert_eq!(404, route(&post("/day/1/part/3", "1 2\n")).status);
+        assert_eq!(404, route(&post("/", "")).status);
+        assert_eq!(422, route(&post("/day/1/part/1", "not numbers")).status);
+
+        let mut request = post("/day/11/part/1", "125 17");
+        request.params = hashmap! {"blinks".to_string() => "many".to_string()};
+        assert_eq!(400, route(&request).status);
+
+        request.method = "GET".to_string();
+        assert_eq!(405, route(&request).status);
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!("mixed", percent_decode("mixed"));
+        assert_eq!("a b/c", percent_decode("a+b%2Fc"));
+        assert_eq!("é", percent_decode("%C3%A9"));
+        assert_eq!("100% sure%2", percent_decode("100%25+sure%2"));
+        assert_eq!("%zz %+1", percent_decode("%zz+%%2B1"));
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!("\"a\\\"b\\\\c\\n\"", json_string("a\"b\\c\n"));
+    }
+
+    #[test]
+    fn test_bind_refuses_non_local_address() {
+        assert!(Server::bind("0.0.0.0:0", 100).is_err());
+    }
+
+    #[test]
+    fn test_turns_away_connections_over_the_limit() {
+        let server = Server::bind("127.0.0.1:0", 1024)
+            .unwrap()
+            .with_max_connections(0);
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+
+        let mut client = TcpStream::connect(address).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 503 Service Unavailable\r\n"));
+    }
+
+    #[test]
+    fn test_serves_local_client() {
+        let server = Server::bind("127.0.0.1:0", 1024).unwrap();
+        let address = server.local_addr().unwrap();
+        thread::spawn(move || server.run());
+
+        let mut client = TcpStream::connect(address).unwrap();
+        let input = "3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n";
+        write!(
+            client,
+            "POST /day/1/part/1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            input.len(),
+            input
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"answer\":\"11\""));
+    }
+}
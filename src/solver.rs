@@ -0,0 +1,147 @@
+use regex::Regex;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// Named parameters for a solver, such as the blink count for day 11.
+pub type Params = HashMap<String, String>;
+
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    UnknownDay(u8),
+    UnknownPart(u8),
+    BadParameter(String),
+    InvalidInput,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownDay(day) => write!(f, "no solver for day {}", day),
+            SolveError::UnknownPart(part) => write!(f, "no part {}", part),
+            SolveError::BadParameter(name) => write!(f, "bad value for parameter {}", name),
+            SolveError::InvalidInput => write!(f, "the solver could not handle this input"),
+        }
+    }
+}
+
+/// A puzzle day that can solve either part for the given input text.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    fn solve(&self, part: u8, input: &str, params: &Params) -> Result<String, SolveError>;
+}
+
+/// Reads a parameter, falling back to `default` when it was not supplied.
+pub fn param<T: FromStr>(params: &Params, name: &str, default: T) -> Result<T, SolveError> {
+    match params.get(name) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| SolveError::BadParameter(name.to_string())),
+        None => Ok(default),
+    }
+}
+
+/// Reads a parameter like `param`, refusing values outside `range` so a request can't ask for
+/// unbounded work.
+pub fn param_in<T: FromStr + PartialOrd>(
+    params: &Params,
+    name: &str,
+    default: T,
+    range: RangeInclusive<T>,
+) -> Result<T, SolveError> {
+    let value = param(params, name, default)?;
+
+    if range.contains(&value) {
+        Ok(value)
+    } else {
+        Err(SolveError::BadParameter(name.to_string()))
+    }
+}
+
+/// Checks that every line of `input` matches `pattern`, so a solver can turn away input its
+/// parser would panic on.
+pub fn check_lines(input: &str, pattern: &str) -> Result<(), SolveError> {
+    let pattern = Regex::new(pattern).unwrap();
+
+    if input.lines().all(|line| pattern.is_match(line)) {
+        Ok(())
+    } else {
+        Err(SolveError::InvalidInput)
+    }
+}
+
+/// Checks that `input` is a non-empty rectangle of characters that are all `allowed`.
+pub fn check_grid(input: &str, allowed: impl Fn(char) -> bool) -> Result<(), SolveError> {
+    let width = input.lines().next().map_or(0, |line| line.chars().count());
+    let valid = width > 0
+        && input
+            .lines()
+            .all(|line| line.chars().count() == width && line.chars().all(&allowed));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(SolveError::InvalidInput)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solver::{check_grid, check_lines, param, param_in, Params, SolveError};
+    use maplit::hashmap;
+
+    #[test]
+    fn test_param() {
+        let params: Params = hashmap! {
+            "blinks".to_string() => "6".to_string(),
+            "width".to_string() => "wide".to_string(),
+        };
+
+        assert_eq!(Ok(6), param(&params, "blinks", 25));
+        assert_eq!(Ok(103), param(&params, "height", 103));
+        assert_eq!(
+            Err(SolveError::BadParameter("width".to_string())),
+            param::<i32>(&params, "width", 101)
+        );
+    }
+
+    #[test]
+    fn test_param_in() {
+        let params: Params = hashmap! {"blinks".to_string() => "6".to_string()};
+
+        assert_eq!(Ok(6), param_in(&params, "blinks", 25, 0..=10));
+        assert_eq!(Ok(25), param_in(&params, "seconds", 25, 0..=100));
+        assert_eq!(
+            Err(SolveError::BadParameter("blinks".to_string())),
+            param_in(&params, "blinks", 25, 7..=10)
+        );
+    }
+
+    #[test]
+    fn test_check_lines() {
+        assert_eq!(Ok(()), check_lines("1|2\n3|4\n", r"^\d+\|\d+$"));
+        assert_eq!(
+            Err(SolveError::InvalidInput),
+            check_lines("1|2\n3,4\n", r"^\d+\|\d+$")
+        );
+    }
+
+    #[test]
+    fn test_check_grid() {
+        let allowed = |c| ".#".contains(c);
+
+        assert_eq!(Ok(()), check_grid("..#\n#..\n", allowed));
+        assert_eq!(
+            Err(SolveError::InvalidInput),
+            check_grid("..#\n#.\n", allowed)
+        );
+        assert_eq!(
+            Err(SolveError::InvalidInput),
+            check_grid("..#\n#.x\n", allowed)
+        );
+        assert_eq!(Err(SolveError::InvalidInput), check_grid("", allowed));
+    }
+}
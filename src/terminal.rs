@@ -0,0 +1,404 @@
+use crate::cli;
+use crate::terminal::Colour::{Blue, Cyan, Green, Grey, Magenta, Red, White, Yellow};
+use crate::terminal::Control::{Faster, Quit, Slower, Step, TogglePause};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::io::{self, Read, Write};
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const DEFAULT_FPS: f64 = 10.0;
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 240.0;
+
+/// A grid simulation that can be drawn with `Display` and advanced one step at a time.
+pub trait Simulation: Display {
+    /// Advances by one step, returning false once there is nothing left to do.
+    fn step(&mut self) -> bool;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Colour {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    fn ansi_code(&self) -> u8 {
+        match self {
+            Red => 31,
+            Green => 32,
+            Yellow => 33,
+            Blue => 34,
+            Magenta => 35,
+            Cyan => 36,
+            White => 97,
+            Grey => 90,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Control {
+    TogglePause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Control {
+    fn from_key(key: u8) -> Option<Control> {
+        match key {
+            b' ' | b'p' => Some(TogglePause),
+            b's' | b'n' => Some(Step),
+            b'+' | b'=' => Some(Faster),
+            b'-' => Some(Slower),
+            b'q' => Some(Quit),
+            _ => None,
+        }
+    }
+}
+
+enum Event {
+    Tick,
+    Key(Control),
+    Closed,
+}
+
+/// Wraps text in the ANSI codes for a colour.
+pub fn paint(text: &str, colour: Colour) -> String {
+    format!("\x1b[{}m{}\x1b[0m", colour.ansi_code(), text)
+}
+
+/// Wraps each character that has an entry in the palette in ANSI colour codes.
+pub fn colourise(frame: &str, palette: &HashMap<char, Colour>) -> String {
+    let mut result = String::with_capacity(frame.len());
+
+    for c in frame.chars() {
+        match palette.get(&c) {
+            Some(colour) => result.push_str(&paint(&c.to_string(), *colour)),
+            None => result.push(c),
+        }
+    }
+
+    result
+}
+
+pub struct Animation {
+    fps: f64,
+    paused: bool,
+    palette: HashMap<char, Colour>,
+}
+
+impl Animation {
+    pub fn new(fps: f64) -> Self {
+        Animation {
+            fps,
+            paused: false,
+            palette: HashMap::new(),
+        }
+    }
+
+    /// Builds an animation when `--animate` was passed, honouring `--fps <n>` and `--paused`.
+    /// Exits with a message on stderr if `--fps` isn't a usable frame rate.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        if !cli::flag(args, "--animate") {
+            return None;
+        }
+
+        let fps = cli::option(args, "--fps")
+            .map(|fps| {
+                parse_fps(fps).unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    process::exit(2)
+                })
+            })
+            .unwrap_or(DEFAULT_FPS);
+
+        Some(Animation::new(fps).starting_paused(cli::flag(args, "--paused")))
+    }
+
+    pub fn starting_paused(self, paused: bool) -> Self {
+        Animation { paused, ..self }
+    }
+
+    pub fn with_palette(self, palette: HashMap<char, Colour>) -> Self {
+        Animation { palette, ..self }
+    }
+
+    /// Plays the simulation on the terminal, reading single key presses from stdin.
+    ///
+    /// Returns the number of steps shown before the simulation finished or was quit.
+    pub fn play<S: Simulation>(&self, simulation: &mut S) -> io::Result<usize> {
+        let raw_mode = RawMode::enable();
+        let (controls, key_reader) = spawn_key_reader();
+        let mut out = io::stdout().lock();
+
+        write!(out, "\x1b[?25l")?;
+        let result = self.run(simulation, &mut out, &controls);
+        write!(out, "\x1b[?25h")?;
+        out.flush()?;
+        drop(raw_mode);
+
+        // The reader is blocked reading stdin and can't be woken, so it is detached rather than
+        // joined. With `controls` gone it stops at the next key press, or with the process.
+        drop(controls);
+        drop(key_reader);
+
+        result
+    }
+
+    /// Drives the simulation, drawing every frame to `out` and reacting to `controls`.
+    pub fn run<S: Simulation, W: Write>(
+        &self,
+        simulation: &mut S,
+        out: &mut W,
+        controls: &Receiver<Control>,
+    ) -> io::Result<usize> {
+        let mut fps = self.fps;
+        let mut paused = self.paused;
+        let mut frame = 0;
+        let mut next_tick = Instant::now() + frame_delay(fps);
+
+        loop {
+            self.draw(out, simulation, frame, fps, paused)?;
+
+            let event = if paused {
+                controls.recv().map_or(Event::Closed, Event::Key)
+            } else {
+                let timeout = next_tick.saturating_duration_since(Instant::now());
+
+                match controls.recv_timeout(timeout) {
+                    Ok(control) => Event::Key(control),
+                    Err(RecvTimeoutError::Timeout) => Event::Tick,
+                    Err(RecvTimeoutError::Disconnected) => {
+                        thread::sleep(timeout);
+                        Event::Tick
+                    }
+                }
+            };
+
+            let advance = match event {
+                Event::Tick => {
+                    next_tick = Instant::now() + frame_delay(fps);
+                    true
+                }
+                Event::Closed | Event::Key(Quit) => return Ok(frame),
+                Event::Key(TogglePause) => {
+                    paused = !paused;
+                    next_tick = Instant::now() + frame_delay(fps);
+                    false
+                }
+                Event::Key(Step) => paused,
+                Event::Key(Faster) => {
+                    fps = (fps * 2.0).min(MAX_FPS);
+                    false
+                }
+                Event::Key(Slower) => {
+                    fps = (fps / 2.0).max(MIN_FPS);
+                    false
+                }
+            };
+
+            if advance {
+                if !simulation.step() {
+                    return Ok(frame);
+                }
+                frame += 1;
+            }
+        }
+    }
+
+    fn draw<S: Simulation, W: Write>(
+        &self,
+        out: &mut W,
+        simulation: &S,
+        frame: usize,
+        fps: f64,
+        paused: bool,
+    ) -> io::Result<()> {
+        write!(out, "\x1b[H\x1b[2J")?;
+        writeln!(
+            out,
+            "frame {} | {} fps{}",
+            frame,
+            fps,
+            if paused { " | paused" } else { "" }
+        )?;
+        write!(out, "{}", colourise(&simulation.to_string(), &self.palette))?;
+        writeln!(out, "[space] pause  [s] step  [+/-] speed  [q] quit")?;
+
+        out.flush()
+    }
+}
+
+// A frame rate has to be a positive, finite number for there to be a delay between frames.
+fn parse_fps(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(fps) if fps.is_finite() && fps > 0.0 => Ok(fps),
+        _ => Err(format!(
+            "--fps expects a positive number of frames per second, not {}",
+            value
+        )),
+    }
+}
+
+fn frame_delay(fps: f64) -> Duration {
+    Duration::from_secs_f64(1.0 / fps)
+}
+
+fn spawn_key_reader() -> (Receiver<Control>, JoinHandle<()>) {
+    let (sender, receiver) = mpsc::channel();
+
+    let reader = thread::spawn(move || {
+        let mut stdin = io::stdin();
+        let mut key = [0u8];
+
+        while let Ok(1) = stdin.read(&mut key) {
+            if let Some(control) = Control::from_key(key[0]) {
+                if sender.send(control).is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    (receiver, reader)
+}
+
+// Switches the terminal to unbuffered input so keys arrive without Enter, if `stty` is around.
+struct RawMode {
+    enabled: bool,
+}
+
+impl RawMode {
+    fn enable() -> Self {
+        RawMode {
+            enabled: stty(&["-icanon", "-echo", "min", "1"]),
+        }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        if self.enabled {
+            stty(&["icanon", "echo"]);
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> bool {
+    Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::terminal::{colourise, parse_fps, Animation, Colour, Control, Simulation};
+    use maplit::hashmap;
+    use rstest::rstest;
+    use std::fmt;
+    use std::sync::mpsc;
+
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl fmt::Display for Counter {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(f, "{}", self.value)
+        }
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                false
+            } else {
+                self.value += 1;
+                true
+            }
+        }
+    }
+
+    #[test]
+    fn test_colourise() {
+        let palette = hashmap! {'#' => Colour::Red};
+
+        assert_eq!("\x1b[31m#\x1b[0m.\n", colourise("#.\n", &palette));
+    }
+
+    #[test]
+    fn test_run_to_completion() {
+        let (sender, controls) = mpsc::channel();
+        drop(sender);
+        let mut counter = Counter { value: 0, limit: 3 };
+        let mut out = vec![];
+
+        let frames = Animation::new(1000.0)
+            .run(&mut counter, &mut out, &controls)
+            .unwrap();
+
+        assert_eq!(3, frames);
+        assert_eq!(3, counter.value);
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("frame 3 | 1000 fps\n3\n"));
+    }
+
+    #[test]
+    fn test_run_paused_steps_and_quits() {
+        let (sender, controls) = mpsc::channel();
+        for control in [Control::Step, Control::Step, Control::Faster, Control::Quit] {
+            sender.send(control).unwrap();
+        }
+        let mut counter = Counter {
+            value: 0,
+            limit: 10,
+        };
+        let mut out = vec![];
+
+        let frames = Animation::new(1.0)
+            .starting_paused(true)
+            .run(&mut counter, &mut out, &controls)
+            .unwrap();
+
+        assert_eq!(2, frames);
+        assert_eq!(2, counter.value);
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("frame 2 | 2 fps | paused\n2\n"));
+    }
+
+    #[test]
+    fn test_from_args() {
+        let args = ["day_6", "--animate", "--fps", "25"].map(String::from);
+
+        assert!(Animation::from_args(&args[..1]).is_none());
+        assert_eq!(25.0, Animation::from_args(&args).unwrap().fps);
+    }
+
+    #[rstest]
+    #[case("0")]
+    #[case("-5")]
+    #[case("inf")]
+    #[case("NaN")]
+    #[case("fast")]
+    fn test_parse_fps_rejects(#[case] value: &str) {
+        assert!(parse_fps(value).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use advent_2024::days::{self, day_13, day_15, day_7};
+use maplit::hashmap;
+
+#[test]
+fn test_solvers_by_day() {
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    assert_eq!(Ok("11".to_string()), days::solve(1, 1, input, &hashmap! {}));
+    assert_eq!(Ok("31".to_string()), days::solve(1, 2, input, &hashmap! {}));
+}
+
+#[test]
+fn test_expressions_built_outside_the_crate() {
+    let expressions = vec![
+        day_7::Expression {
+            numbers: vec![10, 19],
+            result: 190,
+        },
+        day_7::Expression {
+            numbers: vec![15, 6],
+            result: 156,
+        },
+    ];
+
+    assert_eq!(190, day_7::solve_part_1(&expressions));
+    assert_eq!(346, day_7::solve_part_2(&expressions));
+}
+
+#[test]
+fn test_machine_built_outside_the_crate() {
+    let machine = day_13::Machine {
+        button_a: day_13::Vector { x: 94, y: 34 },
+        button_b: day_13::Vector { x: 22, y: 67 },
+        prize: day_13::Vector { x: 8400, y: 5400 },
+    };
+
+    assert_eq!(280, day_13::solve_part_1(&[machine]));
+}
+
+#[test]
+fn test_room_moved_step_by_step() {
+    let (mut room, directions) = day_15::parse_input(
+        "########\n\
+         #..O.O.#\n\
+         ##@.O..#\n\
+         #...O..#\n\
+         #.#.O..#\n\
+         #...O..#\n\
+         #......#\n\
+         ########\n\
+         \n\
+         <^^>>>vv<v>>v<<\n",
+    );
+
+    assert_eq!(day_15::Vector::new(2, 2), room.robot);
+
+    for direction in &directions {
+        room.move_robot(direction);
+    }
+
+    assert_eq!(day_15::Vector::new(4, 4), room.robot);
+    assert_eq!(2028, room.gps_score());
+}